@@ -82,6 +82,7 @@ fn resolve_from_config(config: AlacrittyConfig, config_path: &Path) -> Detection
                 font: Some(font),
                 config_path: Some(config_path.to_path_buf()),
                 profile: None,
+                variant: None,
                 error_reason: None,
                 confidence: Confidence::Certain,
             }
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use super::{config_error, config_home, read_yaml_settings, var};
+use crate::font::{is_nerd_font, normalize_font_name};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Profile used when `default_profile` is unset.
+const DEFAULT_PROFILE: &str = "main";
+/// Contour's built-in `font.regular.family`.
+const DEFAULT_FONT: &str = "monospace";
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Contour, "HOME is not set".to_string(), None),
+    };
+
+    let config_path = config_home(vars, home).join("contour/contour.yml");
+
+    let config = match read_yaml_settings(&config_path) {
+        Ok(config) => config,
+        Err(reason) => return config_error(Terminal::Contour, reason, Some(config_path)),
+    };
+
+    let profile = config
+        .as_ref()
+        .and_then(|config| config.get(&["default_profile"]))
+        .filter(|profile| !profile.is_empty())
+        .unwrap_or(DEFAULT_PROFILE)
+        .to_string();
+
+    // `regular` is either a mapping with a `family` or the family itself.
+    let family = config.as_ref().and_then(|config| {
+        let regular = ["profiles", profile.as_str(), "font", "regular"];
+        config
+            .get(&[regular.as_slice(), &["family"]].concat())
+            .or_else(|| config.get(&regular))
+    });
+    let (font, confidence) = match family.map(normalize_font_name) {
+        Some(font) if !font.is_empty() => (font, Confidence::Certain),
+        _ => (DEFAULT_FONT.to_string(), Confidence::Probable),
+    };
+
+    DetectionResult {
+        detected: Some(is_nerd_font(&font)),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Contour),
+        font: Some(font),
+        config_path: config.is_some().then_some(config_path),
+        profile: Some(profile),
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
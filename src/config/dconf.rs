@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::{config_home, var};
+
+/// A dconf database, as stored in GVDB format at `~/.config/dconf/user`.
+///
+/// Only string and boolean values are decoded; keys of other types are
+/// skipped.
+pub struct Dconf {
+    values: HashMap<String, Value>,
+}
+
+enum Value {
+    String(String),
+    Bool(bool),
+}
+
+/// The desktop's monospace font, used when a terminal follows the system font.
+pub const SYSTEM_MONOSPACE_FONT: &str = "/org/gnome/desktop/interface/monospace-font-name";
+/// Font description used when [`SYSTEM_MONOSPACE_FONT`] is unset. The schema
+/// default varies between distributions, but is never a Nerd Font.
+pub const SYSTEM_DEFAULT_FONT: &str = "Monospace 11";
+
+/// Size of the file header: signature, version, options and root pointer.
+const HEADER_SIZE: usize = 24;
+/// Size of a hash table item: hash, parent, key pointer, type and value pointer.
+const ITEM_SIZE: usize = 24;
+
+impl Dconf {
+    /// Reads a dconf database. Returns:
+    /// - `Ok(Some(db))` if the file exists and was parsed successfully
+    /// - `Ok(None)` if the file does not exist or is inaccessible
+    /// - `Err(reason)` if the file exists but is malformed
+    pub fn read(path: &Path) -> Result<Option<Self>, String> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return Ok(None),
+            Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+        };
+
+        match parse(&data) {
+            Some(values) => Ok(Some(Self { values })),
+            None => Err(format!("failed to parse {}", path.display())),
+        }
+    }
+
+    pub fn string(&self, key: &str) -> Option<&str> {
+        match self.values.get(key)? {
+            Value::String(value) => Some(value),
+            Value::Bool(_) => None,
+        }
+    }
+
+    /// Iterates over the keys that have a string or boolean value.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.values.get(key)? {
+            Value::Bool(value) => Some(*value),
+            Value::String(_) => None,
+        }
+    }
+}
+
+/// Returns the path of the user's dconf database.
+pub fn user_database(vars: &[(String, String)]) -> Option<PathBuf> {
+    let home = var(vars, "HOME").filter(|v| !v.is_empty())?;
+    Some(config_home(vars, Path::new(home)).join("dconf/user"))
+}
+
+/// Parses the root hash table of a GVDB file.
+///
+/// Each item names a path segment and points at its parent item, so full
+/// keys such as `/org/gnome/desktop/interface/monospace-font-name` are
+/// rebuilt by walking up the parents. Values are serialized `GVariant`s of
+/// type `v`: the inner value, a NUL byte, then the inner type string.
+fn parse(data: &[u8]) -> Option<HashMap<String, Value>> {
+    let big_endian = match data.get(..8)? {
+        b"GVariant" => false,
+        b"raVGtnai" => true,
+        _ => return None,
+    };
+    let u32_at = |offset: usize| -> Option<usize> {
+        let bytes: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+        let value = if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+        usize::try_from(value).ok()
+    };
+    let u16_at = |offset: usize| -> Option<usize> {
+        let bytes: [u8; 2] = data.get(offset..offset + 2)?.try_into().ok()?;
+        let value = if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        };
+        Some(usize::from(value))
+    };
+
+    let (root_start, root_end) = (u32_at(HEADER_SIZE - 8)?, u32_at(HEADER_SIZE - 4)?);
+    data.get(root_start..root_end)?;
+
+    // The top 5 bits of the bloom word count hold the bloom shift.
+    let n_bloom_words = u32_at(root_start)? & ((1 << 27) - 1);
+    let n_buckets = u32_at(root_start + 4)?;
+    let items_start = root_start + 8 + 4 * (n_bloom_words + n_buckets);
+    let n_items = root_end.checked_sub(items_start)? / ITEM_SIZE;
+
+    struct Item<'a> {
+        parent: Option<usize>,
+        key: &'a [u8],
+        kind: u8,
+        value: &'a [u8],
+    }
+
+    let items = (0..n_items)
+        .map(|index| {
+            let offset = items_start + index * ITEM_SIZE;
+            let parent = u32_at(offset + 4)?;
+            let key_start = u32_at(offset + 8)?;
+            let key_size = u16_at(offset + 12)?;
+            let kind = *data.get(offset + 14)?;
+            let value = match kind {
+                b'v' => data.get(u32_at(offset + 16)?..u32_at(offset + 20)?)?,
+                _ => &[],
+            };
+            Some(Item {
+                parent: (parent != 0xffff_ffff).then_some(parent),
+                key: data.get(key_start..key_start + key_size)?,
+                kind,
+                value,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let full_key = |index: usize| -> Option<String> {
+        let mut segments = Vec::new();
+        let mut current = Some(index);
+        while let Some(index) = current {
+            // A well-formed file can't nest deeper than it has items.
+            if segments.len() > items.len() {
+                return None;
+            }
+            let item = items.get(index)?;
+            segments.push(std::str::from_utf8(item.key).ok()?);
+            current = item.parent;
+        }
+        Some(segments.into_iter().rev().collect())
+    };
+
+    let mut values = HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        if item.kind != b'v' {
+            continue;
+        }
+        let key = full_key(index)?;
+        if let Some(value) = decode_variant(item.value) {
+            values.insert(key, value);
+        }
+    }
+    Some(values)
+}
+
+fn decode_variant(bytes: &[u8]) -> Option<Value> {
+    let split = bytes.iter().rposition(|&b| b == 0)?;
+    let (body, signature) = (&bytes[..split], &bytes[split + 1..]);
+    match signature {
+        b"s" => {
+            let text = body.strip_suffix(&[0])?;
+            Some(Value::String(String::from_utf8_lossy(text).into_owned()))
+        }
+        b"b" => Some(Value::Bool(body == [1])),
+        _ => None,
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use super::ini::Ini;
+use super::{config_error, config_home, read_ini_settings, var};
+use crate::font::{fontconfig_family, match_font_list};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Font foot uses when `font=` is not set.
+const DEFAULT_FONT: &str = "monospace";
+
+/// How deep `include=` directives are followed.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Foot, "HOME is not set".to_string(), None),
+    };
+
+    let config_home = config_home(vars, home);
+    let config_dirs = var(vars, "XDG_CONFIG_DIRS")
+        .filter(|v| !v.is_empty())
+        .unwrap_or("/etc/xdg");
+
+    let candidates = std::iter::once(config_home).chain(
+        config_dirs
+            .split(':')
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute()),
+    );
+
+    // foot reads the first foot.ini it finds; files are not merged.
+    for candidate in candidates.map(|dir| dir.join("foot/foot.ini")) {
+        let ini = match read_ini_settings(&candidate) {
+            Ok(Some(ini)) => ini,
+            Ok(None) => continue,
+            Err(reason) => return config_error(Terminal::Foot, reason, Some(candidate)),
+        };
+
+        return match main_font(&ini, &candidate, home, 0) {
+            Ok(Some((spec, path))) => resolve_font_list(&spec, path),
+            Ok(None) => default_font(Some(candidate)),
+            Err((reason, path)) => config_error(Terminal::Foot, reason, Some(path)),
+        };
+    }
+
+    default_font(None)
+}
+
+/// Finds the effective `font=` of the main section, expanding `include=`
+/// directives in place. Returns the value and the file it came from.
+fn main_font(
+    ini: &Ini,
+    path: &Path,
+    home: &Path,
+    depth: usize,
+) -> Result<Option<(String, PathBuf)>, (String, PathBuf)> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err((
+            format!("includes nested too deeply in {}", path.display()),
+            path.to_path_buf(),
+        ));
+    }
+
+    // Keys before the first header and under [main] both belong to main.
+    let mut font = None;
+    for (key, value) in ini.section("").chain(ini.section("main")) {
+        match key {
+            "font" => font = Some((unquote(value).to_string(), path.to_path_buf())),
+            "include" => {
+                let include = expand_home(unquote(value), home);
+                match read_ini_settings(&include) {
+                    Ok(Some(included)) => {
+                        if let Some(found) = main_font(&included, &include, home, depth + 1)? {
+                            font = Some(found);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(reason) => return Err((reason, include)),
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(font)
+}
+
+/// `font=` takes a comma-separated list of fontconfig patterns. foot renders
+/// glyphs missing from the primary font from later entries, so any Nerd Font
+/// in the list counts.
+fn resolve_font_list(spec: &str, config_path: PathBuf) -> DetectionResult {
+    let families: Vec<String> = spec
+        .split(',')
+        .map(fontconfig_family)
+        .filter(|family| !family.is_empty())
+        .collect();
+
+    match match_font_list(&families) {
+        Some((font, detected)) => DetectionResult {
+            detected: Some(detected),
+            source: DetectionSource::TerminalConfig,
+            terminal: Some(Terminal::Foot),
+            font: Some(font),
+            config_path: Some(config_path),
+            profile: None,
+            variant: None,
+            error_reason: None,
+            confidence: Confidence::Certain,
+        },
+        None => config_error(
+            Terminal::Foot,
+            "empty font list".to_string(),
+            Some(config_path),
+        ),
+    }
+}
+
+/// Without `font=`, foot asks fontconfig for `monospace`, which is not a Nerd
+/// Font unless the system's fontconfig says so.
+fn default_font(config_path: Option<PathBuf>) -> DetectionResult {
+    DetectionResult {
+        detected: Some(false),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Foot),
+        font: Some(DEFAULT_FONT.to_string()),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence: Confidence::Probable,
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn expand_home(path: &str, home: &Path) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,131 @@
+use super::config_error;
+use super::dconf::{self, Dconf, SYSTEM_DEFAULT_FONT, SYSTEM_MONOSPACE_FONT};
+use crate::font::{match_font_list, pango_families};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Settings path of GNOME Terminal's profile list and profiles.
+const GNOME_TERMINAL_PROFILES: &str = "/org/gnome/terminal/legacy/profiles:/";
+/// UUID of the profile GNOME Terminal creates on first run.
+const GNOME_TERMINAL_DEFAULT_PROFILE: &str = "b1dcc9dd-5262-4d8d-a863-c897e6d979b9";
+/// Ptyxis keeps its font settings application-wide rather than per profile.
+const PTYXIS: &str = "/org/gnome/Ptyxis/";
+/// Settings path of Tilix's profile list and profiles.
+const TILIX_PROFILES: &str = "/com/gexperts/Tilix/profiles/";
+/// UUID of the profile Tilix creates on first run.
+const TILIX_DEFAULT_PROFILE: &str = "2b7c4080-0ddd-46c5-8f23-563fd3ba789d";
+
+/// Font descriptions used when the keys are unset.
+const GNOME_TERMINAL_DEFAULT_FONT: &str = "Monospace 12";
+const PTYXIS_DEFAULT_FONT: &str = "Monospace 11";
+const TILIX_DEFAULT_FONT: &str = "Monospace 12";
+
+/// Where a terminal keeps its font within dconf.
+struct FontKeys {
+    use_system_font: String,
+    font: String,
+    default_font: &'static str,
+    profile: Option<String>,
+}
+
+pub fn resolve_gnome_terminal(vars: &[(String, String)]) -> DetectionResult {
+    resolve(Terminal::GnomeTerminal, vars, |dconf| {
+        let uuid = dconf
+            .string(&format!("{GNOME_TERMINAL_PROFILES}default"))
+            .unwrap_or(GNOME_TERMINAL_DEFAULT_PROFILE);
+        let prefix = format!("{GNOME_TERMINAL_PROFILES}:{uuid}/");
+        FontKeys {
+            use_system_font: format!("{prefix}use-system-font"),
+            font: format!("{prefix}font"),
+            default_font: GNOME_TERMINAL_DEFAULT_FONT,
+            profile: dconf
+                .string(&format!("{prefix}visible-name"))
+                .filter(|name| !name.is_empty())
+                .map(ToString::to_string),
+        }
+    })
+}
+
+pub fn resolve_ptyxis(vars: &[(String, String)]) -> DetectionResult {
+    resolve(Terminal::Ptyxis, vars, |_| FontKeys {
+        use_system_font: format!("{PTYXIS}use-system-font"),
+        font: format!("{PTYXIS}font-name"),
+        default_font: PTYXIS_DEFAULT_FONT,
+        profile: None,
+    })
+}
+
+/// Tilix doesn't tell the shell which profile it runs; `launch_profile` is
+/// the profile name given on Tilix's command line, if any.
+pub fn resolve_tilix(vars: &[(String, String)], launch_profile: Option<&str>) -> DetectionResult {
+    resolve(Terminal::Tilix, vars, |dconf| {
+        let uuid = launch_profile
+            .and_then(|name| {
+                dconf.keys().find_map(|key| {
+                    let uuid = key
+                        .strip_prefix(TILIX_PROFILES)?
+                        .strip_suffix("/visible-name")?;
+                    (dconf.string(key) == Some(name)).then_some(uuid)
+                })
+            })
+            .or_else(|| dconf.string(&format!("{TILIX_PROFILES}default")))
+            .unwrap_or(TILIX_DEFAULT_PROFILE);
+        let prefix = format!("{TILIX_PROFILES}{uuid}/");
+        FontKeys {
+            use_system_font: format!("{prefix}use-system-font"),
+            font: format!("{prefix}font"),
+            default_font: TILIX_DEFAULT_FONT,
+            profile: dconf
+                .string(&format!("{prefix}visible-name"))
+                .filter(|name| !name.is_empty())
+                .map(ToString::to_string),
+        }
+    })
+}
+
+fn resolve(
+    terminal: Terminal,
+    vars: &[(String, String)],
+    font_keys: impl FnOnce(&Dconf) -> FontKeys,
+) -> DetectionResult {
+    let Some(config_path) = dconf::user_database(vars) else {
+        return config_error(terminal, "HOME is not set".to_string(), None);
+    };
+
+    let dconf = match Dconf::read(&config_path) {
+        Ok(Some(dconf)) => dconf,
+        Ok(None) => return config_error(terminal, "no settings file found".to_string(), None),
+        Err(reason) => return config_error(terminal, reason, Some(config_path)),
+    };
+
+    let keys = font_keys(&dconf);
+    // These terminals follow the system font until told otherwise.
+    let (description, confidence) = if dconf.bool(&keys.use_system_font).unwrap_or(true) {
+        match dconf.string(SYSTEM_MONOSPACE_FONT) {
+            Some(font) => (font, Confidence::Certain),
+            None => (SYSTEM_DEFAULT_FONT, Confidence::Probable),
+        }
+    } else {
+        let font = dconf.string(&keys.font).unwrap_or(keys.default_font);
+        (font, Confidence::Certain)
+    };
+
+    let Some((font, detected)) = match_font_list(&pango_families(description)) else {
+        return config_error(
+            terminal,
+            "no font configured".to_string(),
+            Some(config_path),
+        );
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(terminal),
+        font: Some(font),
+        config_path: Some(config_path),
+        profile: keys.profile,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{config_error, config_home, read_json5_settings, read_settings_file, var};
+use crate::font::{css_font_families, match_font_list};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Hyper's built-in `fontFamily`.
+const DEFAULT_FONT_FAMILY: &str =
+    r#"Menlo, "DejaVu Sans Mono", Consolas, "Lucida Console", monospace"#;
+
+#[derive(Deserialize)]
+struct HyperJson {
+    #[serde(default)]
+    config: Option<HyperConfig>,
+}
+
+#[derive(Deserialize)]
+struct HyperConfig {
+    #[serde(default, rename = "fontFamily")]
+    font_family: Option<String>,
+}
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Hyper, "HOME is not set".to_string(), None),
+    };
+
+    let config_dir = if cfg!(target_os = "macos") {
+        home.join("Library/Application Support/Hyper")
+    } else {
+        config_home(vars, home).join("Hyper")
+    };
+
+    // Hyper 4 keeps its config in hyper.json; earlier versions use .hyper.js,
+    // which moved from the home directory into the config directory in 3.1.
+    let json_path = config_dir.join("hyper.json");
+    match read_json5_settings::<HyperJson>(&json_path) {
+        Ok(Some(settings)) => {
+            let font_family = settings.config.and_then(|config| config.font_family);
+            return resolve_font_family(font_family.as_deref(), Some(json_path));
+        }
+        Ok(None) => {}
+        Err(reason) => return config_error(Terminal::Hyper, reason, Some(json_path)),
+    }
+
+    for js_path in [config_dir.join(".hyper.js"), home.join(".hyper.js")] {
+        let content = match read_settings_file(&js_path) {
+            Ok(Some(content)) => content,
+            Ok(None) => continue,
+            Err(reason) => return config_error(Terminal::Hyper, reason, Some(js_path)),
+        };
+        return match extract_font_family(&content) {
+            Ok(font_family) => resolve_font_family(font_family.as_deref(), Some(js_path)),
+            Err(error) => config_error(
+                Terminal::Hyper,
+                format!(
+                    "failed to parse {} at line {} column {}: {}",
+                    js_path.display(),
+                    error.line,
+                    error.column,
+                    error.reason
+                ),
+                Some(js_path),
+            ),
+        };
+    }
+
+    resolve_font_family(None, None)
+}
+
+/// Hyper renders with Chromium, which takes glyphs missing from the first
+/// font from later entries of the list.
+fn resolve_font_family(font_family: Option<&str>, config_path: Option<PathBuf>) -> DetectionResult {
+    let (font_family, confidence) = match font_family {
+        Some(font_family) => (font_family, Confidence::Certain),
+        None => (DEFAULT_FONT_FAMILY, Confidence::Probable),
+    };
+
+    let Some((font, detected)) = match_font_list(&css_font_families(font_family)) else {
+        return config_error(
+            Terminal::Hyper,
+            "no font configured".to_string(),
+            config_path,
+        );
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Hyper),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
+
+/// Why `fontFamily` couldn't be extracted, and where.
+struct ExtractError {
+    line: usize,
+    column: usize,
+    reason: &'static str,
+}
+
+impl ExtractError {
+    /// Locates byte `offset` of `source` as a 1-based line and column.
+    fn at(source: &str, offset: usize, reason: &'static str) -> Self {
+        let before = &source[..offset];
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count()
+                + 1,
+            reason,
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    /// A template literal with substitutions, whose value isn't known.
+    Template,
+    Punct(char),
+}
+
+/// Pulls `fontFamily` out of `module.exports = { config: { ... } }` or
+/// `module.exports.config = { ... }` without running the script. Only a plain
+/// string literal is accepted as the value.
+fn extract_font_family(source: &str) -> Result<Option<String>, ExtractError> {
+    let tokens = tokenize(source)?;
+
+    let config = (0..tokens.len())
+        .find_map(|start| {
+            let ident = |offset: usize, name: &str| {
+                matches!(tokens.get(start + offset), Some((Token::Ident(ident), _)) if ident == name)
+            };
+            let punct = |offset: usize, c: char| {
+                tokens.get(start + offset).map(|(token, _)| token) == Some(&Token::Punct(c))
+            };
+            if !(ident(0, "module") && punct(1, '.') && ident(2, "exports")) {
+                return None;
+            }
+            if punct(3, '=') && punct(4, '{') {
+                Some(find_property(&tokens, start + 4, "config"))
+            } else if punct(3, '.') && ident(4, "config") && punct(5, '=') {
+                Some((start + 6 < tokens.len()).then_some(start + 6))
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| {
+            ExtractError::at(
+                source,
+                source.len(),
+                "no `module.exports = {` or `module.exports.config =` found",
+            )
+        })?;
+
+    let Some(config) = config else {
+        return Ok(None);
+    };
+    let (token, offset) = &tokens[config];
+    if *token != Token::Punct('{') {
+        return Err(ExtractError::at(
+            source,
+            *offset,
+            "`config` is not an object literal",
+        ));
+    }
+
+    let Some(value) = find_property(&tokens, config, "fontFamily") else {
+        return Ok(None);
+    };
+    match (&tokens[value], tokens.get(value + 1)) {
+        ((Token::Str(font_family), _), None | Some((Token::Punct(',' | '}'), _))) => {
+            Ok(Some(font_family.clone()))
+        }
+        ((_, offset), _) => Err(ExtractError::at(
+            source,
+            *offset,
+            "`fontFamily` is not a string literal",
+        )),
+    }
+}
+
+/// Finds `key: value` directly inside the object literal opened at `open`,
+/// returning the index of the value's first token.
+fn find_property(tokens: &[(Token, usize)], open: usize, key: &str) -> Option<usize> {
+    let mut depth = 0;
+    for index in open + 1..tokens.len() {
+        match &tokens[index].0 {
+            Token::Punct('{' | '[' | '(') => depth += 1,
+            Token::Punct('}' | ']' | ')') if depth == 0 => return None,
+            Token::Punct('}' | ']' | ')') => depth -= 1,
+            Token::Ident(name) | Token::Str(name)
+                if depth == 0
+                    && name == key
+                    && matches!(tokens[index - 1].0, Token::Punct('{' | ','))
+                    && tokens.get(index + 1).map(|(token, _)| token)
+                        == Some(&Token::Punct(':')) =>
+            {
+                return (index + 2 < tokens.len()).then_some(index + 2);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits JavaScript source into the tokens the extractor cares about, each
+/// with its byte offset. Comments and whitespace are dropped.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExtractError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let unterminated = |offset, reason| ExtractError::at(source, offset, reason);
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if chars.peek().is_some_and(|(_, next)| *next == '/') => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            }
+            '/' if chars.peek().is_some_and(|(_, next)| *next == '*') => {
+                chars.next();
+                let mut previous = ' ';
+                loop {
+                    let (_, c) = chars
+                        .next()
+                        .ok_or_else(|| unterminated(start, "unterminated comment"))?;
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '"' | '\'' | '`' => {
+                let mut value = String::new();
+                let mut substitution = false;
+                loop {
+                    let (_, next) = chars
+                        .next()
+                        .ok_or_else(|| unterminated(start, "unterminated string"))?;
+                    match next {
+                        '\\' => {
+                            let (_, escaped) = chars
+                                .next()
+                                .ok_or_else(|| unterminated(start, "unterminated string"))?;
+                            value.push(match escaped {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => other,
+                            });
+                        }
+                        '\n' if c != '`' => {
+                            return Err(unterminated(start, "unterminated string"));
+                        }
+                        '$' if c == '`' && chars.peek().is_some_and(|(_, c)| *c == '{') => {
+                            substitution = true;
+                            value.push(next);
+                        }
+                        next if next == c => break,
+                        next => value.push(next),
+                    }
+                }
+                let token = if substitution {
+                    Token::Template
+                } else {
+                    Token::Str(value)
+                };
+                tokens.push((token, start));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let mut ident = c.to_string();
+                while let Some((_, c)) =
+                    chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '$')
+                {
+                    ident.push(c);
+                }
+                tokens.push((Token::Ident(ident), start));
+            }
+            c => tokens.push((Token::Punct(c), start)),
+        }
+    }
+    Ok(tokens)
+}
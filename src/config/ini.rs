@@ -0,0 +1,51 @@
+/// A parsed INI-style config file, as used by KDE, foot and others.
+///
+/// Lines are `key=value` pairs grouped under `[section]` headers; keys before
+/// the first header belong to the section `""`. Comments start with `#` or
+/// `;`. Later assignments of the same key win.
+pub struct Ini {
+    entries: Vec<(String, String, String)>,
+}
+
+impl Ini {
+    pub fn parse(content: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut section = String::new();
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = header.trim().to_string();
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                entries.push((
+                    section.clone(),
+                    key.trim().to_string(),
+                    value.trim().to_string(),
+                ));
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Returns the last value of `key` in `section`.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(s, k, _)| s == section && k == key)
+            .map(|(_, _, value)| value.as_str())
+    }
+
+    /// Returns the `(key, value)` pairs of `section` in file order.
+    pub fn section<'a>(&'a self, section: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.entries
+            .iter()
+            .filter(move |(s, _, _)| s == section)
+            .map(|(_, key, value)| (key.as_str(), value.as_str()))
+    }
+}
@@ -112,6 +112,7 @@ fn resolve_from_plist(config_path: PathBuf, iterm_profile: Option<String>) -> De
         font: Some(font),
         config_path: Some(config_path),
         profile,
+        variant: None,
         error_reason: None,
         confidence: Confidence::Certain,
     }
@@ -138,6 +139,7 @@ fn config_error(
         font: None,
         config_path,
         profile,
+        variant: None,
         error_reason: Some(reason),
         confidence: Confidence::Certain,
     }
@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use super::xml::{self, Tag};
+use super::{config_error, config_home, read_settings_file, var};
+use crate::font::{match_font_list, normalize_font_name};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Files under `options/` that may set the terminal's font, most specific
+/// first. The terminal uses the console font, and the console the editor font,
+/// unless told otherwise.
+const FONT_FILES: &[&str] = &["terminal-font.xml", "console-font.xml", "editor-font.xml"];
+
+/// Font the IDEs ship with and use when no font has been chosen.
+const DEFAULT_FONT: &str = "JetBrains Mono";
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::JetBrains, "HOME is not set".to_string(), None),
+    };
+
+    let config_home = if cfg!(target_os = "macos") {
+        home.join("Library/Application Support")
+    } else {
+        config_home(vars, home)
+    };
+    // Android Studio is published by Google and keeps its config there.
+    let vendor_dirs = [config_home.join("JetBrains"), config_home.join("Google")];
+
+    let Some(product_dir) =
+        inferred_product_dir(vars, &vendor_dirs).or_else(|| newest_product_dir(&vendor_dirs))
+    else {
+        return config_error(
+            Terminal::JetBrains,
+            "no JetBrains config directory found".to_string(),
+            None,
+        );
+    };
+
+    for file in FONT_FILES {
+        let path = product_dir.join("options").join(file);
+        let content = match read_settings_file(&path) {
+            Ok(Some(content)) => content,
+            Ok(None) => continue,
+            Err(reason) => return config_error(Terminal::JetBrains, reason, Some(path)),
+        };
+        let families = match font_families(&content) {
+            Ok(families) => families,
+            Err(reason) => {
+                return config_error(
+                    Terminal::JetBrains,
+                    format!("failed to parse {}: {reason}", path.display()),
+                    Some(path),
+                );
+            }
+        };
+
+        // The fallback font covers glyphs missing from the primary one.
+        if let Some((font, detected)) = match_font_list(&families) {
+            return DetectionResult {
+                detected: Some(detected),
+                source: DetectionSource::TerminalConfig,
+                terminal: Some(Terminal::JetBrains),
+                font: Some(font),
+                config_path: Some(path),
+                profile: None,
+                variant: None,
+                error_reason: None,
+                confidence: Confidence::Certain,
+            };
+        }
+    }
+
+    // The color scheme can also set a font, so the default isn't certain.
+    DetectionResult {
+        detected: Some(false),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::JetBrains),
+        font: Some(DEFAULT_FONT.to_string()),
+        config_path: None,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence: Confidence::Probable,
+    }
+}
+
+/// The IDE points its shell at a history file under its own cache directory,
+/// e.g. `~/.cache/JetBrains/PyCharm2024.1/terminal/history/...`, whose name
+/// matches the config directory of the same IDE version.
+fn inferred_product_dir(vars: &[(String, String)], vendor_dirs: &[PathBuf]) -> Option<PathBuf> {
+    let history = var(vars, "__INTELLIJ_COMMAND_HISTFILE__").filter(|v| !v.is_empty())?;
+    Path::new(history)
+        .components()
+        .filter_map(|component| component.as_os_str().to_str())
+        .filter(|name| product_version(name).is_some())
+        .flat_map(|name| vendor_dirs.iter().map(move |dir| dir.join(name)))
+        .find(|dir| dir.is_dir())
+}
+
+/// Picks the product directory with the highest version, e.g.
+/// `IntelliJIdea2024.2` over `GoLand2024.1`.
+fn newest_product_dir(vendor_dirs: &[PathBuf]) -> Option<PathBuf> {
+    vendor_dirs
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.filter_map(Result::ok))
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            Some((product_version(&name)?, name, entry.path()))
+        })
+        .max()
+        .map(|(_, _, path)| path)
+}
+
+/// Parses the version suffix of a product directory name such as
+/// `RustRover2024.3` into `[2024, 3]`.
+fn product_version(name: &str) -> Option<Vec<u32>> {
+    let product = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let version = &name[product.len()..];
+    if product.is_empty() || version.is_empty() {
+        return None;
+    }
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Reads the primary and fallback font families from a font options file.
+fn font_families(content: &str) -> Result<Vec<String>, String> {
+    let mut primary = None;
+    let mut fallback = None;
+    for tag in xml::tags(content)? {
+        let Tag::Open {
+            name, attributes, ..
+        } = tag
+        else {
+            continue;
+        };
+        if name != "option" {
+            continue;
+        }
+        let value = attributes
+            .get("value")
+            .map(|value| normalize_font_name(value));
+        match attributes.get("name").map(String::as_str) {
+            Some("FONT_FAMILY") => primary = value,
+            Some("SECONDARY_FONT_FAMILY") => fallback = value,
+            _ => {}
+        }
+    }
+
+    Ok(primary
+        .into_iter()
+        .chain(fallback)
+        .filter(|family| !family.is_empty())
+        .collect())
+}
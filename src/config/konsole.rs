@@ -0,0 +1,206 @@
+use std::path::{Path, PathBuf};
+
+use super::{config_error, config_home, data_home, read_ini_settings, var};
+use crate::font::{is_nerd_font, qt_font_family};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Font of the built-in profile when KDE's fixed font isn't configured.
+const DEFAULT_FONT: &str = "Hack";
+
+/// How deep `Parent=` chains between profiles are followed.
+const MAX_PARENTS: usize = 8;
+
+/// The profile a session was started with.
+enum ProfileRef {
+    /// A profile name, from `KONSOLE_PROFILE_NAME`.
+    Name(String),
+    /// A profile file name, from `DefaultProfile` in the rc file.
+    File(String),
+}
+
+pub fn resolve(terminal: Terminal, vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(terminal, "HOME is not set".to_string(), None),
+    };
+    let config_home = config_home(vars, home);
+    let profile_dirs = profile_dirs(vars, home);
+
+    let profile_ref = match var(vars, "KONSOLE_PROFILE_NAME").filter(|v| !v.is_empty()) {
+        Some(name) => Some(ProfileRef::Name(name.to_string())),
+        None => match default_profile(&terminal, &config_home) {
+            Ok(file) => file.map(ProfileRef::File),
+            Err((reason, path)) => return config_error(terminal, reason, Some(path)),
+        },
+    };
+
+    let profile_path = match &profile_ref {
+        Some(ProfileRef::Name(name)) => find_profile_by_name(&profile_dirs, name),
+        Some(ProfileRef::File(file)) => find_profile_file(&profile_dirs, file),
+        None => None,
+    };
+
+    let Some(profile_path) = profile_path else {
+        return match profile_ref {
+            Some(ProfileRef::Name(name) | ProfileRef::File(name)) => profile_error(
+                &terminal,
+                format!("missing profile {name}"),
+                Some(name),
+                None,
+            ),
+            // No profile was ever created, so the built-in one is in use.
+            None => system_fixed_font(terminal, &config_home),
+        };
+    };
+
+    let mut profile = None;
+    let mut read_path = profile_path.clone();
+    let mut path = profile_path;
+    for _ in 0..MAX_PARENTS {
+        let ini = match read_ini_settings(&path) {
+            Ok(Some(ini)) => ini,
+            // Konsole ignores a parent that no longer exists.
+            Ok(None) => break,
+            Err(reason) => return profile_error(&terminal, reason, profile, Some(path)),
+        };
+        read_path.clone_from(&path);
+
+        profile = profile.or_else(|| ini.get("General", "Name").map(ToString::to_string));
+
+        if let Some(font) = ini.get("Appearance", "Font").and_then(qt_font_family) {
+            return DetectionResult {
+                detected: Some(is_nerd_font(&font)),
+                source: DetectionSource::TerminalConfig,
+                terminal: Some(terminal),
+                font: Some(font),
+                config_path: Some(path),
+                profile,
+                variant: None,
+                error_reason: None,
+                confidence: Confidence::Certain,
+            };
+        }
+
+        // Profiles inherit unset keys from their parent, which is either
+        // another profile file or `FALLBACK/` for the built-in profile.
+        match ini.get("General", "Parent").map(Path::new) {
+            Some(parent) if parent.is_absolute() => path = parent.to_path_buf(),
+            _ => break,
+        }
+    }
+
+    // Without a font anywhere in the chain, the built-in profile's applies.
+    let fallback = system_fixed_font(terminal, &config_home);
+    DetectionResult {
+        profile,
+        config_path: fallback.config_path.clone().or(Some(read_path)),
+        ..fallback
+    }
+}
+
+/// Reads `DefaultProfile` from the rc file. Yakuake keeps its own setting and
+/// falls back to Konsole's.
+fn default_profile(
+    terminal: &Terminal,
+    config_home: &Path,
+) -> Result<Option<String>, (String, PathBuf)> {
+    let rc_files: &[&str] = match terminal {
+        Terminal::Yakuake => &["yakuakerc", "konsolerc"],
+        _ => &["konsolerc"],
+    };
+
+    for rc_file in rc_files {
+        let path = config_home.join(rc_file);
+        let ini = read_ini_settings(&path).map_err(|reason| (reason, path.clone()))?;
+        if let Some(profile) = ini
+            .as_ref()
+            .and_then(|ini| ini.get("Desktop Entry", "DefaultProfile"))
+            .filter(|v| !v.is_empty())
+        {
+            return Ok(Some(profile.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn find_profile_file(profile_dirs: &[PathBuf], file: &str) -> Option<PathBuf> {
+    profile_dirs
+        .iter()
+        .map(|dir| dir.join(file))
+        .find(|path| path.is_file())
+}
+
+/// Profile files are usually named after the profile, but a renamed profile
+/// keeps its old file name, so fall back to matching `Name=`.
+fn find_profile_by_name(profile_dirs: &[PathBuf], name: &str) -> Option<PathBuf> {
+    find_profile_file(profile_dirs, &format!("{name}.profile")).or_else(|| {
+        profile_dirs
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.filter_map(Result::ok).map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "profile"))
+            .find(|path| {
+                read_ini_settings(path)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|ini| ini.get("General", "Name") == Some(name))
+            })
+    })
+}
+
+/// The built-in profile uses KDE's fixed-width system font from `kdeglobals`.
+fn system_fixed_font(terminal: Terminal, config_home: &Path) -> DetectionResult {
+    let path = config_home.join("kdeglobals");
+    let fixed = read_ini_settings(&path)
+        .ok()
+        .flatten()
+        .and_then(|ini| ini.get("General", "fixed").and_then(qt_font_family));
+
+    let (font, config_path, confidence) = match fixed {
+        Some(font) => (font, Some(path), Confidence::Certain),
+        None => (DEFAULT_FONT.to_string(), None, Confidence::Probable),
+    };
+
+    DetectionResult {
+        detected: Some(is_nerd_font(&font)),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(terminal),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
+
+/// Returns `konsole/` under the XDG data home and data dirs, in lookup order.
+fn profile_dirs(vars: &[(String, String)], home: &Path) -> Vec<PathBuf> {
+    let data_home = data_home(vars, home);
+    let data_dirs = var(vars, "XDG_DATA_DIRS")
+        .filter(|v| !v.is_empty())
+        .unwrap_or("/usr/local/share:/usr/share");
+
+    std::iter::once(data_home)
+        .chain(
+            data_dirs
+                .split(':')
+                .map(PathBuf::from)
+                .filter(|p| p.is_absolute()),
+        )
+        .map(|dir| dir.join("konsole"))
+        .collect()
+}
+
+/// A config error that still names the profile being read.
+fn profile_error(
+    terminal: &Terminal,
+    reason: String,
+    profile: Option<String>,
+    config_path: Option<PathBuf>,
+) -> DetectionResult {
+    DetectionResult {
+        profile,
+        ..config_error(terminal.clone(), reason, config_path)
+    }
+}
@@ -2,25 +2,89 @@ use std::path::{Path, PathBuf};
 
 use serde::de::DeserializeOwned;
 
-use crate::{Confidence, DetectionResult, DetectionSource, Terminal, var};
+use crate::{Confidence, Context, DetectionResult, DetectionSource, Terminal, var};
 
 mod alacritty;
+mod contour;
+mod dconf;
+mod foot;
+mod gnome_terminal;
+mod hyper;
+mod ini;
 mod iterm2;
+mod jetbrains;
+mod konsole;
+mod rio;
+mod sfnt;
+mod tabby;
 mod terminal_app;
+mod terminator;
+mod termux;
 mod vscode;
+mod warp;
+mod wave;
+mod windows_terminal;
+mod wsl;
+mod xfce4_terminal;
+mod xfconf;
+mod xml;
+mod xrdb;
+mod xresources;
+mod yaml;
 mod zed;
 
-pub fn resolve(terminal: Terminal, vars: &[(String, String)]) -> DetectionResult {
+pub fn resolve(
+    terminal: Terminal,
+    vars: &[(String, String)],
+    context: &Context,
+) -> DetectionResult {
+    let options = context.options;
     match terminal {
         Terminal::Alacritty => alacritty::resolve(vars),
+        Terminal::Contour => contour::resolve(vars),
+        Terminal::Foot => foot::resolve(vars),
+        Terminal::GnomeTerminal => gnome_terminal::resolve_gnome_terminal(vars),
+        Terminal::Hyper => hyper::resolve(vars),
         Terminal::ITerm2 => iterm2::resolve(vars),
+        Terminal::JetBrains => jetbrains::resolve(vars),
+        Terminal::Konsole | Terminal::Yakuake => konsole::resolve(terminal, vars),
+        Terminal::Ptyxis => gnome_terminal::resolve_ptyxis(vars),
+        Terminal::Rio => rio::resolve(vars),
+        Terminal::Tabby => tabby::resolve(vars),
         Terminal::TerminalApp => terminal_app::resolve(vars),
-        Terminal::Vscode => vscode::resolve(vars),
-        Terminal::Zed => zed::resolve(vars),
+        Terminal::Terminator => {
+            terminator::resolve(vars, launch_profile(context, &["terminator"]).as_deref())
+        }
+        Terminal::Termux => termux::resolve(vars),
+        Terminal::Tilix => {
+            gnome_terminal::resolve_tilix(vars, launch_profile(context, &["tilix"]).as_deref())
+        }
+        Terminal::Vscode => vscode::resolve(
+            vars,
+            options.windows_root(),
+            working_dir(context).as_deref(),
+        ),
+        Terminal::Warp => warp::resolve(vars),
+        Terminal::Wave => wave::resolve(vars),
+        Terminal::WindowsTerminal => windows_terminal::resolve(vars, options.windows_root()),
+        Terminal::Urxvt => xresources::resolve_urxvt(vars),
+        Terminal::Xfce4Terminal => xfce4_terminal::resolve(vars),
+        Terminal::Xterm => xresources::resolve_xterm(vars),
+        Terminal::Zed => zed::resolve(vars, working_dir(context).as_deref()),
         _ => no_resolver(terminal),
     }
 }
 
+/// Names the kind of remote session `terminal` renders for, when its client
+/// is on another machine (e.g. a VS Code window attached over SSH or to a dev
+/// container).
+pub fn remote_kind(terminal: Option<&Terminal>, vars: &[(String, String)]) -> Option<&'static str> {
+    match terminal {
+        Some(Terminal::Vscode) => vscode::remote_kind(vars),
+        _ => None,
+    }
+}
+
 fn no_resolver(terminal: Terminal) -> DetectionResult {
     DetectionResult {
         detected: None,
@@ -29,6 +93,7 @@ fn no_resolver(terminal: Terminal) -> DetectionResult {
         font: None,
         config_path: None,
         profile: None,
+        variant: None,
         error_reason: None,
         confidence: Confidence::Certain,
     }
@@ -36,6 +101,40 @@ fn no_resolver(terminal: Terminal) -> DetectionResult {
 
 // --- Shared helpers ---
 
+/// The result for a terminal whose client settings aren't reachable, with the
+/// kind of remote session as its `variant`.
+pub(crate) fn remote_session(terminal: Terminal, kind: &str) -> DetectionResult {
+    DetectionResult {
+        detected: None,
+        source: DetectionSource::RemoteSession,
+        terminal: Some(terminal),
+        font: None,
+        config_path: None,
+        profile: None,
+        variant: Some(kind.to_string()),
+        error_reason: None,
+        confidence: Confidence::Certain,
+    }
+}
+
+/// Returns `$XDG_CONFIG_HOME`, or `~/.config` when it is unset. Relative
+/// paths are ignored, as the XDG base directory spec requires.
+pub(crate) fn config_home(vars: &[(String, String)], home: &Path) -> PathBuf {
+    xdg_base_dir(vars, "XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config"))
+}
+
+/// Returns `$XDG_DATA_HOME`, or `~/.local/share` when it is unset.
+pub(crate) fn data_home(vars: &[(String, String)], home: &Path) -> PathBuf {
+    xdg_base_dir(vars, "XDG_DATA_HOME").unwrap_or_else(|| home.join(".local/share"))
+}
+
+fn xdg_base_dir(vars: &[(String, String)], key: &str) -> Option<PathBuf> {
+    var(vars, key)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+}
+
 pub(crate) fn config_error(
     terminal: Terminal,
     reason: String,
@@ -48,11 +147,63 @@ pub(crate) fn config_error(
         font: None,
         config_path,
         profile: None,
+        variant: None,
         error_reason: Some(reason),
         confidence: Confidence::Certain,
     }
 }
 
+/// Returns the profile passed with `-p`/`--profile` to the nearest ancestor
+/// named one of `names`. Terminator and Tilix both take the flag.
+fn launch_profile(context: &Context, names: &[&str]) -> Option<String> {
+    let procfs = &context.procfs;
+    let emulator = procfs.ancestors(context.pid?).find(|process| {
+        names.contains(&process.comm.as_str()) || names.contains(&process.name())
+    })?;
+
+    let args = procfs.cmdline(emulator.pid)?;
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(profile) = arg.strip_prefix("--profile=") {
+            return Some(profile.to_string());
+        }
+        if arg == "-p" || arg == "--profile" {
+            return args.next().cloned();
+        }
+    }
+    None
+}
+
+/// Returns the directory detection runs in: the working directory of the
+/// `--pid` process, or else our own. Editors' project settings are looked up
+/// from here.
+fn working_dir(context: &Context) -> Option<PathBuf> {
+    match context.options.pid() {
+        Some(pid) => context.procfs.cwd(pid),
+        None => std::env::current_dir().ok(),
+    }
+}
+
+/// Returns `start` and each of its ancestors, nearest first. Inside `home`
+/// the walk stops at `home`, since files further up aren't part of the user's
+/// projects. Both are canonicalized first, so a home reached through a
+/// symlink still bounds the walk.
+fn ancestor_dirs(start: Option<&Path>, home: Option<&Path>) -> impl Iterator<Item = PathBuf> {
+    let canonical =
+        |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let start = start.map(canonical);
+    let home = home
+        .map(canonical)
+        .filter(|home| start.as_ref().is_some_and(|start| start.starts_with(home)));
+    let dirs: Vec<PathBuf> = start
+        .iter()
+        .flat_map(|start| start.ancestors())
+        .take_while(|dir| home.as_ref().is_none_or(|home| dir.starts_with(home)))
+        .map(Path::to_path_buf)
+        .collect();
+    dirs.into_iter()
+}
+
 /// Read a settings file from disk. Returns:
 /// - `Ok(Some(content))` if the file exists and was read successfully
 /// - `Ok(None)` if the file does not exist or is inaccessible
@@ -110,3 +261,24 @@ pub(crate) fn read_toml_settings<T: DeserializeOwned>(path: &Path) -> Result<Opt
         Err(e) => Err(format!("failed to parse {}: {e}", path.display())),
     }
 }
+
+/// Read and parse an INI-style settings file. Returns:
+/// - `Ok(Some(settings))` if the file exists and was read successfully
+/// - `Ok(None)` if the file does not exist or is inaccessible
+/// - `Err(reason)` if the file exists but could not be read
+fn read_ini_settings(path: &Path) -> Result<Option<ini::Ini>, String> {
+    Ok(read_settings_file(path)?.map(|content| ini::Ini::parse(&content)))
+}
+
+/// Read and parse a YAML settings file. Returns:
+/// - `Ok(Some(settings))` if the file exists and was parsed successfully
+/// - `Ok(None)` if the file does not exist or is inaccessible
+/// - `Err(reason)` if the file exists but is malformed
+fn read_yaml_settings(path: &Path) -> Result<Option<yaml::Yaml>, String> {
+    let Some(content) = read_settings_file(path)? else {
+        return Ok(None);
+    };
+    yaml::Yaml::parse(&content)
+        .map(Some)
+        .map_err(|line| format!("failed to parse {} at line {line}", path.display()))
+}
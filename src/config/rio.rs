@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{config_error, config_home, read_toml_settings, var};
+use crate::font::{match_font_list, normalize_font_name};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Rio's built-in font, used when `[fonts] family` is unset.
+const DEFAULT_FONT: &str = "Cascadia Code";
+
+#[derive(Deserialize)]
+struct RioConfig {
+    #[serde(default)]
+    fonts: Option<RioFonts>,
+}
+
+#[derive(Deserialize)]
+struct RioFonts {
+    #[serde(default)]
+    family: Option<String>,
+    #[serde(default)]
+    regular: Option<RioFont>,
+    #[serde(default)]
+    extras: Vec<RioFont>,
+}
+
+#[derive(Deserialize)]
+struct RioFont {
+    #[serde(default)]
+    family: Option<String>,
+}
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Rio, "HOME is not set".to_string(), None),
+    };
+
+    // Rio reads ~/.config/rio on every Unix, macOS included, unless
+    // RIO_CONFIG_HOME points elsewhere.
+    let config_dir = var(vars, "RIO_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| config_home(vars, home).join("rio"));
+    let config_path = config_dir.join("config.toml");
+
+    let config = match read_toml_settings::<RioConfig>(&config_path) {
+        Ok(config) => config,
+        Err(reason) => return config_error(Terminal::Rio, reason, Some(config_path)),
+    };
+    let config_path = config.is_some().then_some(config_path);
+    let fonts = config.and_then(|config| config.fonts);
+
+    let non_empty = |family: Option<String>| {
+        family
+            .map(|family| normalize_font_name(&family))
+            .filter(|family| !family.is_empty())
+    };
+    let (primary, extras) = match fonts {
+        Some(fonts) => (
+            // A family for the regular style overrides the shared one.
+            non_empty(fonts.regular.and_then(|regular| regular.family))
+                .or_else(|| non_empty(fonts.family)),
+            fonts
+                .extras
+                .into_iter()
+                .filter_map(|extra| non_empty(extra.family))
+                .collect(),
+        ),
+        None => (None, Vec::new()),
+    };
+    let confidence = if primary.is_some() {
+        Confidence::Certain
+    } else {
+        Confidence::Probable
+    };
+
+    // Glyphs missing from the main font are taken from `[[fonts.extras]]`.
+    let primary = primary.unwrap_or_else(|| DEFAULT_FONT.to_string());
+    let families: Vec<String> = std::iter::once(primary.clone()).chain(extras).collect();
+    let (font, detected) = match_font_list(&families).unwrap_or((primary, false));
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Rio),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
@@ -0,0 +1,114 @@
+// Platforms of name records. Unicode and Windows names are UTF-16BE.
+const PLATFORM_UNICODE: u16 = 0;
+const PLATFORM_MACINTOSH: u16 = 1;
+const PLATFORM_WINDOWS: u16 = 3;
+/// Language ID of Windows names in US English.
+const WINDOWS_ENGLISH_US: u16 = 0x0409;
+
+const NAME_FAMILY: u16 = 1;
+const NAME_FULL: u16 = 4;
+const NAME_POSTSCRIPT: u16 = 6;
+const NAME_TYPOGRAPHIC_FAMILY: u16 = 16;
+
+/// Names read from the `name` table of a TrueType or OpenType font.
+pub struct FontNames {
+    /// The typographic family (name ID 16), or else the legacy family (1).
+    pub family: Option<String>,
+    /// The full font name (name ID 4), e.g. `Hack Nerd Font Mono Bold`.
+    pub full_name: Option<String>,
+    /// The PostScript name (name ID 6), e.g. `HackNFM-Bold`.
+    pub postscript_name: Option<String>,
+}
+
+/// Reads the names of a font file's first font. Collections (`.ttc`) are
+/// accepted too.
+pub fn font_names(data: &[u8]) -> Result<FontNames, String> {
+    let font = match data.get(..4) {
+        Some(b"ttcf") => read_u32(data, 12).ok_or("truncated collection header")? as usize,
+        Some([0, 1, 0, 0] | b"OTTO" | b"true") => 0,
+        Some(_) => return Err("not a TrueType or OpenType font".to_string()),
+        None => return Err("truncated font header".to_string()),
+    };
+
+    let num_tables = read_u16(data, font + 4).ok_or("truncated font header")?;
+    let name = (0..usize::from(num_tables))
+        .map(|index| font + 12 + index * 16)
+        .find(|&record| data.get(record..record + 4) == Some(b"name"))
+        .ok_or("no name table")?;
+    let offset = read_u32(data, name + 8).ok_or("truncated table directory")? as usize;
+    let length = read_u32(data, name + 12).ok_or("truncated table directory")? as usize;
+    let table = offset
+        .checked_add(length)
+        .and_then(|end| data.get(offset..end))
+        .ok_or("name table extends past the end of the file")?;
+
+    let count = read_u16(table, 2).ok_or("truncated name table")?;
+    let strings = usize::from(read_u16(table, 4).ok_or("truncated name table")?);
+
+    let mut names = Vec::new();
+    for index in 0..usize::from(count) {
+        let record = 6 + index * 12;
+        let field = |at| read_u16(table, record + at).ok_or("truncated name record");
+        let (platform, encoding, language, name_id) = (field(0)?, field(2)?, field(4)?, field(6)?);
+        let (length, start) = (usize::from(field(8)?), usize::from(field(10)?));
+        let bytes = table
+            .get(strings + start..strings + start + length)
+            .ok_or("name string extends past the name table")?;
+        let Some(value) = decode(platform, encoding, bytes) else {
+            continue;
+        };
+        names.push((name_id, rank(platform, language), value));
+    }
+
+    // Picks the most widely readable record of each name.
+    let best = |name_id| {
+        names
+            .iter()
+            .filter(|(id, _, value)| *id == name_id && !value.trim().is_empty())
+            .min_by_key(|(_, rank, _)| *rank)
+            .map(|(_, _, value)| value.trim().to_string())
+    };
+
+    Ok(FontNames {
+        family: best(NAME_TYPOGRAPHIC_FAMILY).or_else(|| best(NAME_FAMILY)),
+        full_name: best(NAME_FULL),
+        postscript_name: best(NAME_POSTSCRIPT),
+    })
+}
+
+/// Orders records by preference: US English Windows names, then other
+/// Windows or Unicode names, then Macintosh ones.
+fn rank(platform: u16, language: u16) -> u8 {
+    match (platform, language) {
+        (PLATFORM_WINDOWS, WINDOWS_ENGLISH_US) => 0,
+        (PLATFORM_WINDOWS | PLATFORM_UNICODE, _) => 1,
+        _ => 2,
+    }
+}
+
+fn decode(platform: u16, encoding: u16, bytes: &[u8]) -> Option<String> {
+    match (platform, encoding) {
+        (PLATFORM_UNICODE, _) | (PLATFORM_WINDOWS, 0 | 1 | 10) => {
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16(&units).ok()
+        }
+        // Mac Roman agrees with ASCII, which font names almost always are.
+        (PLATFORM_MACINTOSH, 0) => bytes
+            .is_ascii()
+            .then(|| String::from_utf8_lossy(bytes).into_owned()),
+        _ => None,
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
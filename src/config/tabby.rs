@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use super::{config_error, config_home, read_yaml_settings, var};
+use crate::font::{match_font_list, normalize_font_name};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Tabby's built-in `terminal.font` for this platform.
+const DEFAULT_FONT: &str = if cfg!(target_os = "macos") {
+    "Menlo"
+} else if cfg!(windows) {
+    "Consolas"
+} else {
+    "Liberation Mono"
+};
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Tabby, "HOME is not set".to_string(), None),
+    };
+
+    let config_path = if cfg!(target_os = "macos") {
+        home.join("Library/Application Support/tabby")
+    } else {
+        config_home(vars, home).join("tabby")
+    }
+    .join("config.yaml");
+
+    let config = match read_yaml_settings(&config_path) {
+        Ok(config) => config,
+        Err(reason) => return config_error(Terminal::Tabby, reason, Some(config_path)),
+    };
+
+    let setting = |key| {
+        config
+            .as_ref()
+            .and_then(|config| config.get(&["terminal", key]))
+            .map(normalize_font_name)
+            .filter(|family| !family.is_empty())
+    };
+    let (font, confidence) = match setting("font") {
+        Some(font) => (font, Confidence::Certain),
+        None => (DEFAULT_FONT.to_string(), Confidence::Probable),
+    };
+
+    // `fallbackFont` supplies glyphs missing from the main font.
+    let families: Vec<String> = std::iter::once(font.clone())
+        .chain(setting("fallbackFont"))
+        .collect();
+    let (font, detected) = match_font_list(&families).unwrap_or((font, false));
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Tabby),
+        font: Some(font),
+        config_path: config.is_some().then_some(config_path),
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
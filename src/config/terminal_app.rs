@@ -49,6 +49,7 @@ fn resolve_from_plist(config_path: PathBuf) -> DetectionResult {
         font: Some(font),
         config_path: Some(config_path),
         profile: Some(profile),
+        variant: None,
         error_reason: None,
         confidence: Confidence::Certain,
     }
@@ -104,6 +105,7 @@ fn config_error(
         font: None,
         config_path,
         profile,
+        variant: None,
         error_reason: Some(reason),
         confidence: Confidence::Certain,
     }
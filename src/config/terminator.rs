@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+use super::dconf::{self, Dconf, SYSTEM_DEFAULT_FONT, SYSTEM_MONOSPACE_FONT};
+use super::{config_error, config_home, read_settings_file, var};
+use crate::font::{match_font_list, pango_families};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Profile Terminator uses unless started with `--profile`.
+const DEFAULT_PROFILE: &str = "default";
+/// Font description of the built-in profile.
+const DEFAULT_FONT: &str = "Mono 10";
+
+/// A parsed ConfigObj file, as written by Terminator.
+///
+/// Sections nest by the number of brackets: `[profiles]` contains
+/// `[[default]]`, which may contain `[[[child]]]`, and so on.
+struct ConfigObj {
+    sections: Vec<Vec<String>>,
+    entries: Vec<(Vec<String>, String, String)>,
+}
+
+impl ConfigObj {
+    fn parse(content: &str) -> Result<Self, usize> {
+        let mut sections = Vec::new();
+        let mut entries = Vec::new();
+        let mut current: Vec<String> = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                let depth = line.chars().take_while(|&c| c == '[').count();
+                let name = line[depth..]
+                    .strip_suffix(&"]".repeat(depth))
+                    .filter(|_| depth <= current.len() + 1)
+                    .ok_or(index + 1)?;
+                current.truncate(depth - 1);
+                current.push(unquote(name.trim()).to_string());
+                sections.push(current.clone());
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or(index + 1)?;
+            entries.push((
+                current.clone(),
+                unquote(key.trim()).to_string(),
+                parse_value(value.trim()).to_string(),
+            ));
+        }
+
+        Ok(Self { sections, entries })
+    }
+
+    /// Returns the last value of `key` directly in the section at `path`.
+    fn get(&self, path: &[&str], key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(s, k, _)| s.iter().map(String::as_str).eq(path.iter().copied()) && k == key)
+            .map(|(_, _, value)| value.as_str())
+    }
+
+    fn has_section(&self, path: &[&str]) -> bool {
+        self.sections
+            .iter()
+            .any(|s| s.iter().map(String::as_str).eq(path.iter().copied()))
+    }
+}
+
+/// `launch_profile` is the profile given on Terminator's command line, if any.
+pub fn resolve(vars: &[(String, String)], launch_profile: Option<&str>) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Terminator, "HOME is not set".to_string(), None),
+    };
+    let config_path = config_home(vars, home).join("terminator/config");
+
+    let config = match read_settings_file(&config_path) {
+        Ok(Some(content)) => match ConfigObj::parse(&content) {
+            Ok(config) => Some(config),
+            Err(line) => {
+                return config_error(
+                    Terminal::Terminator,
+                    format!("failed to parse {} at line {line}", config_path.display()),
+                    Some(config_path),
+                );
+            }
+        },
+        Ok(None) => None,
+        Err(reason) => return config_error(Terminal::Terminator, reason, Some(config_path)),
+    };
+
+    // Terminator falls back to the default profile when the requested one
+    // doesn't exist.
+    let profile = launch_profile
+        .filter(|name| {
+            config
+                .as_ref()
+                .is_some_and(|config| config.has_section(&["profiles", name]))
+        })
+        .unwrap_or(DEFAULT_PROFILE)
+        .to_string();
+    let section = ["profiles", profile.as_str()];
+    let get = |key| config.as_ref().and_then(|config| config.get(&section, key));
+
+    let use_system_font = get("use_system_font").is_none_or(|v| v.eq_ignore_ascii_case("true"));
+    let config_path = config.as_ref().map(|_| config_path);
+    let (description, config_path, confidence) = if use_system_font {
+        system_font(vars, config_path)
+    } else {
+        match get("font") {
+            Some(font) => (font.to_string(), config_path, Confidence::Certain),
+            None => (DEFAULT_FONT.to_string(), config_path, Confidence::Probable),
+        }
+    };
+
+    let Some((font, detected)) = match_font_list(&pango_families(&description)) else {
+        return DetectionResult {
+            profile: Some(profile),
+            ..config_error(
+                Terminal::Terminator,
+                "no font configured".to_string(),
+                config_path,
+            )
+        };
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Terminator),
+        font: Some(font),
+        config_path,
+        profile: Some(profile),
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
+
+/// Terminator asks GSettings for the desktop's monospace font.
+fn system_font(
+    vars: &[(String, String)],
+    config_path: Option<PathBuf>,
+) -> (String, Option<PathBuf>, Confidence) {
+    let system = dconf::user_database(vars).and_then(|path| {
+        let dconf = Dconf::read(&path).ok()??;
+        let font = dconf.string(SYSTEM_MONOSPACE_FONT)?.to_string();
+        Some((font, path))
+    });
+
+    match system {
+        Some((font, path)) => (font, Some(path), Confidence::Certain),
+        None => (
+            SYSTEM_DEFAULT_FONT.to_string(),
+            config_path,
+            Confidence::Probable,
+        ),
+    }
+}
+
+/// Strips quotes from a value, or an inline comment from an unquoted one.
+fn parse_value(value: &str) -> &str {
+    match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+        _ => value.split(" #").next().unwrap_or_default().trim(),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
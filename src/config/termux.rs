@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use super::sfnt::font_names;
+use super::{config_error, var};
+use crate::font::is_nerd_font;
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Without `~/.termux/font.ttf`, Termux renders with Android's monospace
+/// system font, which has no Nerd Font glyphs.
+const DEFAULT_FONT: &str = "monospace";
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Termux, "HOME is not set".to_string(), None),
+    };
+
+    let font_path = home.join(".termux/font.ttf");
+    let data = match std::fs::read(&font_path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return DetectionResult {
+                detected: Some(false),
+                source: DetectionSource::TerminalConfig,
+                terminal: Some(Terminal::Termux),
+                font: Some(DEFAULT_FONT.to_string()),
+                config_path: None,
+                profile: None,
+                variant: None,
+                error_reason: None,
+                confidence: Confidence::Certain,
+            };
+        }
+        Err(e) => {
+            return config_error(
+                Terminal::Termux,
+                format!("failed to read {}: {e}", font_path.display()),
+                Some(font_path),
+            );
+        }
+    };
+
+    let names = match font_names(&data) {
+        Ok(names) => names,
+        Err(reason) => {
+            return config_error(
+                Terminal::Termux,
+                format!("failed to parse {}: {reason}", font_path.display()),
+                Some(font_path),
+            );
+        }
+    };
+
+    // Patched fonts don't always rename every record, so any name counts.
+    let candidates = [names.family, names.full_name, names.postscript_name];
+    let font = candidates
+        .iter()
+        .flatten()
+        .find(|name| is_nerd_font(name))
+        .or_else(|| candidates.iter().flatten().next())
+        .cloned();
+    let Some(font) = font else {
+        return config_error(
+            Terminal::Termux,
+            format!("no font name in {}", font_path.display()),
+            Some(font_path),
+        );
+    };
+
+    DetectionResult {
+        detected: Some(is_nerd_font(&font)),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Termux),
+        font: Some(font),
+        config_path: Some(font_path),
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence: Confidence::Certain,
+    }
+}
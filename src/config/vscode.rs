@@ -1,11 +1,23 @@
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
-use super::{config_error, read_json5_settings, var};
-use crate::font::{is_nerd_font, normalize_font_name};
+use super::wsl::{is_wsl, windows_home};
+use super::{ancestor_dirs, config_error, read_json5_settings, remote_session, var};
+use crate::font::{css_font_families, match_font_list};
 use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
 
+/// The first family of VS Code's default `editor.fontFamily`, used when
+/// neither font setting is set.
+const DEFAULT_FONT: &str = if cfg!(target_os = "macos") {
+    "Menlo"
+} else if cfg!(windows) {
+    "Consolas"
+} else {
+    "Droid Sans Mono"
+};
+
 #[derive(Deserialize)]
 struct VscodeSettings {
     #[serde(default, rename = "terminal.integrated.fontFamily")]
@@ -14,102 +26,605 @@ struct VscodeSettings {
     editor_font_family: Option<String>,
 }
 
-/// Known VS Code forks and their identifying information.
-/// To add a new fork (e.g. Cursor), add an entry here.
+/// A `.code-workspace` file, whose `settings` apply to the whole workspace.
+#[derive(Deserialize)]
+struct CodeWorkspace {
+    #[serde(default)]
+    folders: Vec<WorkspaceFolder>,
+    #[serde(default)]
+    settings: Option<VscodeSettings>,
+}
+
+#[derive(Deserialize)]
+struct WorkspaceFolder {
+    /// Relative to the workspace file's directory, or absolute. Folders given
+    /// by `uri` instead are on other file systems.
+    #[serde(default)]
+    path: Option<String>,
+}
+
+impl CodeWorkspace {
+    /// Whether `dir` is one of the folders of the workspace stored at `path`.
+    fn contains(&self, path: &Path, dir: &Path) -> bool {
+        let (Some(root), Ok(dir)) = (path.parent(), std::fs::canonicalize(dir)) else {
+            return false;
+        };
+        self.folders
+            .iter()
+            .filter_map(|folder| folder.path.as_deref())
+            .filter_map(|folder| std::fs::canonicalize(root.join(folder)).ok())
+            .any(|folder| folder == dir)
+    }
+}
+
+/// A settings file that applies to the session.
+struct SettingsLayer {
+    path: PathBuf,
+    settings: VscodeSettings,
+}
+
+/// Known VS Code forks and builds, and what identifies each.
+/// To add a new fork, add an entry here.
 struct VscodeFork {
+    /// Name reported as the result's `variant`.
+    name: &'static str,
     /// Directory name used for user settings (e.g. "Code", "VSCodium").
     app_dir: &'static str,
-    /// Case-insensitive substrings to match in `VSCODE_GIT_ASKPASS_NODE`.
-    askpass_substrings: &'static [&'static str],
+    /// Case-insensitive names of the executables and macOS app bundles found
+    /// in `VSCODE_GIT_ASKPASS_NODE`.
+    path_names: &'static [&'static str],
+    /// Directories the remote server is installed in, under the home
+    /// directory of the remote machine.
+    server_dirs: &'static [&'static str],
+    /// macOS bundle identifiers, as set in `__CFBundleIdentifier`.
+    bundle_ids: &'static [&'static str],
+    /// Flatpak app IDs; each keeps its settings in
+    /// `~/.var/app/<id>/config/<app_dir>`.
+    flatpak_ids: &'static [&'static str],
+    /// Snap names; each may keep its settings in
+    /// `~/snap/<name>/current/.config/<app_dir>`.
+    snaps: &'static [&'static str],
+    /// Env vars that only this fork sets in its terminals.
+    env_vars: &'static [&'static str],
+    /// Suffix of `TERM_PROGRAM_VERSION` that only this build has.
+    version_suffix: Option<&'static str>,
 }
 
 const KNOWN_FORKS: &[VscodeFork] = &[
     VscodeFork {
+        name: "Visual Studio Code",
         app_dir: "Code",
-        askpass_substrings: &["code"],
+        path_names: &["code", "visual studio code.app"],
+        server_dirs: &[".vscode-server"],
+        bundle_ids: &["com.microsoft.VSCode"],
+        flatpak_ids: &["com.visualstudio.code"],
+        snaps: &["code"],
+        env_vars: &[],
+        version_suffix: None,
     },
     VscodeFork {
+        name: "Visual Studio Code - Insiders",
+        app_dir: "Code - Insiders",
+        path_names: &[
+            "code-insiders",
+            "code - insiders",
+            "visual studio code - insiders.app",
+        ],
+        server_dirs: &[".vscode-server-insiders"],
+        bundle_ids: &["com.microsoft.VSCodeInsiders"],
+        flatpak_ids: &["com.visualstudio.code.insiders"],
+        snaps: &["code-insiders"],
+        env_vars: &[],
+        version_suffix: Some("-insider"),
+    },
+    VscodeFork {
+        name: "VSCodium",
         app_dir: "VSCodium",
-        askpass_substrings: &["codium"],
+        path_names: &["codium", "vscodium", "vscodium.app"],
+        server_dirs: &[".vscodium-server"],
+        bundle_ids: &["com.vscodium"],
+        flatpak_ids: &["com.vscodium.codium"],
+        snaps: &["codium"],
+        env_vars: &[],
+        version_suffix: None,
+    },
+    VscodeFork {
+        name: "Cursor",
+        app_dir: "Cursor",
+        path_names: &["cursor", "cursor.app"],
+        server_dirs: &[".cursor-server"],
+        bundle_ids: &["com.todesktop.230313mzl4w4u92"],
+        flatpak_ids: &[],
+        snaps: &[],
+        env_vars: &["CURSOR_TRACE_ID"],
+        version_suffix: None,
+    },
+    VscodeFork {
+        name: "Windsurf",
+        app_dir: "Windsurf",
+        path_names: &["windsurf", "windsurf.app"],
+        server_dirs: &[".windsurf-server"],
+        bundle_ids: &["com.exafunction.windsurf"],
+        flatpak_ids: &[],
+        snaps: &[],
+        env_vars: &[],
+        version_suffix: None,
+    },
+    VscodeFork {
+        name: "Positron",
+        app_dir: "Positron",
+        path_names: &["positron", "positron.app"],
+        server_dirs: &[".positron-server"],
+        bundle_ids: &["com.rstudio.positron"],
+        flatpak_ids: &[],
+        snaps: &[],
+        env_vars: &[],
+        version_suffix: None,
+    },
+    VscodeFork {
+        name: "Code - OSS",
+        app_dir: "Code - OSS",
+        path_names: &["code-oss", "code - oss.app"],
+        server_dirs: &[".vscode-oss-server"],
+        bundle_ids: &["com.visualstudio.code.oss"],
+        flatpak_ids: &["com.visualstudio.code-oss"],
+        snaps: &[],
+        env_vars: &[],
+        version_suffix: None,
     },
 ];
 
-/// Resolves the app-specific directory name for user settings from env vars.
-///
-/// Checks `VSCODE_GIT_ASKPASS_NODE` for known fork substrings. Returns an
-/// error if the env var is missing or doesn't match any known fork.
-fn resolve_app_dir(vars: &[(String, String)]) -> Result<&'static str, String> {
-    let askpass = var(vars, "VSCODE_GIT_ASKPASS_NODE")
-        .filter(|v| !v.is_empty())
-        .ok_or_else(|| "VSCODE_GIT_ASKPASS_NODE is not set".to_string())?;
-
-    let askpass_lower = askpass.to_ascii_lowercase();
-    KNOWN_FORKS
-        .iter()
-        .find(|f| {
-            f.askpass_substrings
+/// Identifies the fork from env vars, trying the most specific signals
+/// first: env vars only one fork sets (such as `CURSOR_TRACE_ID`), the macOS
+/// bundle ID, the Flatpak app ID, the components of
+/// `VSCODE_GIT_ASKPASS_NODE`, and finally the `TERM_PROGRAM_VERSION` suffix
+/// of Insiders builds. Returns an error if none of them names a known fork.
+fn resolve_fork(vars: &[(String, String)]) -> Result<&'static VscodeFork, String> {
+    let non_empty = |name| var(vars, name).filter(|v| !v.is_empty());
+    let find = |matches: &dyn Fn(&VscodeFork) -> bool| KNOWN_FORKS.iter().find(|f| matches(f));
+    let askpass = non_empty("VSCODE_GIT_ASKPASS_NODE");
+
+    let by_env = || find(&|fork| fork.env_vars.iter().any(|name| non_empty(name).is_some()));
+    let by_bundle_id = || {
+        let bundle_id = non_empty("__CFBundleIdentifier")?;
+        find(&|fork| {
+            fork.bundle_ids
                 .iter()
-                .any(|s| askpass_lower.contains(s))
+                .any(|id| id.eq_ignore_ascii_case(bundle_id))
+        })
+    };
+    let by_flatpak_id = || {
+        let app_id = flatpak_id(vars)?;
+        find(&|fork| fork.flatpak_ids.contains(&app_id))
+    };
+    // The deepest component wins, so a user named "code" doesn't turn
+    // `/home/code/Applications/cursor/cursor` into VS Code.
+    let by_askpass = || {
+        path_components(askpass?).rev().find_map(|component| {
+            let component = component.to_ascii_lowercase();
+            let component = component.strip_suffix(".exe").unwrap_or(&component);
+            find(&|fork| {
+                fork.path_names.contains(&component) || fork.server_dirs.contains(&component)
+            })
+        })
+    };
+    // Insiders builds say so in their version, e.g. `1.96.0-insider`.
+    let by_version = || {
+        let version = non_empty("TERM_PROGRAM_VERSION")?;
+        find(&|fork| {
+            fork.version_suffix
+                .is_some_and(|suffix| version.ends_with(suffix))
+        })
+    };
+
+    by_env()
+        .or_else(by_bundle_id)
+        .or_else(by_flatpak_id)
+        .or_else(by_askpass)
+        .or_else(by_version)
+        .ok_or_else(|| match askpass {
+            Some(askpass) => format!("unrecognized VSCODE_GIT_ASKPASS_NODE: {askpass}"),
+            None => "VSCODE_GIT_ASKPASS_NODE is not set".to_string(),
         })
-        .map(|f| f.app_dir)
-        .ok_or_else(|| format!("unrecognized VSCODE_GIT_ASKPASS_NODE: {askpass}"))
 }
 
-/// Returns the platform-specific path to the user settings.json for the given app directory.
-fn user_settings_path(home: &Path, app_dir: &str) -> std::path::PathBuf {
+/// Names the kind of remote session the terminal belongs to, when the window
+/// rendering it runs on another machine: `codespaces`, `dev-container`, `ssh`,
+/// or `server` when only the remote server's own markers give it away. A WSL
+/// window isn't one, since its Windows-side settings can be read.
+pub fn remote_kind(vars: &[(String, String)]) -> Option<&'static str> {
+    let non_empty = |name| var(vars, name).filter(|v| !v.is_empty());
+    let is_true = |name| var(vars, name).is_some_and(|v| v == "true");
+
+    if is_true("CODESPACES") {
+        return Some("codespaces");
+    }
+    if is_true("REMOTE_CONTAINERS") || non_empty("REMOTE_CONTAINERS_IPC").is_some() {
+        return Some("dev-container");
+    }
+    if is_wsl(vars) {
+        return None;
+    }
+    if non_empty("SSH_CONNECTION").is_some() {
+        return Some("ssh");
+    }
+
+    // The server keeps its copy of the askpass helper under e.g.
+    // `~/.vscode-server/bin/<commit>/node`.
+    let in_server_dir = non_empty("VSCODE_GIT_ASKPASS_NODE").is_some_and(|askpass| {
+        path_components(askpass).any(|component| {
+            let component = component.to_ascii_lowercase();
+            KNOWN_FORKS
+                .iter()
+                .any(|fork| fork.server_dirs.contains(&component.as_str()))
+        })
+    });
+    (in_server_dir || non_empty("VSCODE_AGENT_FOLDER").is_some()).then_some("server")
+}
+
+/// Splits a Unix or Windows path into its components.
+fn path_components(path: &str) -> impl DoubleEndedIterator<Item = &str> {
+    path.split(['/', '\\']).filter(|c| !c.is_empty())
+}
+
+/// Returns the Flatpak app ID the terminal runs under, from `FLATPAK_ID` or
+/// the `.../app/<id>/vscode-ipc-*.sock` path of `VSCODE_IPC_HOOK_CLI`.
+fn flatpak_id(vars: &[(String, String)]) -> Option<&str> {
+    if let Some(id) = var(vars, "FLATPAK_ID").filter(|v| !v.is_empty()) {
+        return Some(id);
+    }
+    let hook = var(vars, "VSCODE_IPC_HOOK_CLI")?;
+    let components: Vec<&str> = path_components(hook).collect();
+    components
+        .windows(2)
+        .find(|pair| pair[0] == "app")
+        .map(|pair| pair[1])
+}
+
+/// Returns the platform-specific path to the `User` directory for the given app directory.
+fn user_dir(home: &Path, app_dir: &str) -> PathBuf {
     if cfg!(target_os = "macos") {
-        home.join(format!(
-            "Library/Application Support/{app_dir}/User/settings.json"
-        ))
+        home.join(format!("Library/Application Support/{app_dir}/User"))
     } else {
-        home.join(format!(".config/{app_dir}/User/settings.json"))
+        home.join(format!(".config/{app_dir}/User"))
     }
 }
 
-pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
-    let home = match var(vars, "HOME") {
-        Some(value) if !value.is_empty() => value,
-        _ => return config_error(Terminal::Vscode, "HOME is not set".to_string(), None),
+/// Picks the `User` directory of a native, Flatpak or Snap install, whichever
+/// exists, trying the packaging the session runs under first. Falls back to
+/// the native directory.
+fn installed_user_dir(vars: &[(String, String)], home: &Path, fork: &VscodeFork) -> PathBuf {
+    let native = user_dir(home, fork.app_dir);
+    if cfg!(target_os = "macos") {
+        return native;
+    }
+
+    let flatpak = |id: &str| {
+        home.join(".var/app")
+            .join(id)
+            .join("config")
+            .join(fork.app_dir)
+            .join("User")
+    };
+    let snap = |name: &str| {
+        home.join("snap")
+            .join(name)
+            .join("current/.config")
+            .join(fork.app_dir)
+            .join("User")
     };
 
-    let app_dir = match resolve_app_dir(vars) {
-        Ok(dir) => dir,
+    let mut candidates = Vec::new();
+    if let Some(id) = flatpak_id(vars).filter(|id| fork.flatpak_ids.contains(id)) {
+        candidates.push(flatpak(id));
+    }
+    let askpass = var(vars, "VSCODE_GIT_ASKPASS_NODE").unwrap_or_default();
+    if let Some(name) = fork
+        .snaps
+        .iter()
+        .find(|name| askpass.starts_with(&format!("/snap/{name}/")))
+    {
+        candidates.push(snap(name));
+    }
+    candidates.push(native.clone());
+    candidates.extend(fork.flatpak_ids.iter().map(|id| flatpak(id)));
+    candidates.extend(fork.snaps.iter().map(|name| snap(name)));
+
+    candidates
+        .into_iter()
+        .find(|dir| dir.is_dir())
+        .unwrap_or(native)
+}
+
+/// Returns the `User` directory of the Windows-side app, which hosts the
+/// VS Code window of a WSL remote session.
+fn windows_user_dir(windows_home: &Path, app_dir: &str) -> PathBuf {
+    windows_home.join(format!("AppData/Roaming/{app_dir}/User"))
+}
+
+pub fn resolve(
+    vars: &[(String, String)],
+    windows_root: &Path,
+    cwd: Option<&Path>,
+) -> DetectionResult {
+    // Fonts are rendered by the client, whose settings aren't on this machine.
+    if let Some(kind) = remote_kind(vars) {
+        return remote_session(Terminal::Vscode, kind);
+    }
+
+    let fork = match resolve_fork(vars) {
+        Ok(fork) => fork,
         Err(reason) => return config_error(Terminal::Vscode, reason, None),
     };
+    let variant = Some(fork.name.to_string());
+    let error = |reason: String, path: Option<PathBuf>| DetectionResult {
+        variant: variant.clone(),
+        ..config_error(Terminal::Vscode, reason, path)
+    };
 
-    let config_path = user_settings_path(Path::new(home), app_dir);
+    let wsl = is_wsl(vars);
+    let user_dir = if wsl {
+        match windows_home(vars, windows_root) {
+            Ok(home) => windows_user_dir(&home, fork.app_dir),
+            Err(reason) => return error(reason, None),
+        }
+    } else {
+        match var(vars, "HOME") {
+            Some(home) if !home.is_empty() => installed_user_dir(vars, Path::new(home), fork),
+            _ => return error("HOME is not set".to_string(), None),
+        }
+    };
 
-    let settings = match read_json5_settings::<VscodeSettings>(&config_path) {
-        Ok(Some(s)) => s,
-        Ok(None) => {
-            return config_error(Terminal::Vscode, "no settings file found".to_string(), None);
+    let profile = match active_profile(&user_dir, cwd) {
+        Ok(profile) => profile,
+        Err((reason, path)) => return error(reason, Some(path)),
+    };
+    let profile_name = profile.as_ref().map(|profile| profile.name.clone());
+
+    let home = var(vars, "HOME")
+        .filter(|home| !home.is_empty())
+        .map(Path::new);
+    let layers = match settings_layers(cwd, home, &user_dir, profile) {
+        Ok(layers) => layers,
+        Err((reason, path)) => return error(reason, Some(path)),
+    };
+    // Each key comes from the most specific layer that sets it, even to an
+    // empty list, which VS Code then treats as unset. Chromium takes glyphs
+    // missing from the first font from later entries of the list.
+    let setting = |key: fn(&VscodeSettings) -> Option<&String>| {
+        let (value, path) = layers
+            .iter()
+            .find_map(|layer| Some((key(&layer.settings)?, &layer.path)))?;
+        Some((match_font_list(&css_font_families(value))?, path))
+    };
+    let font_name = setting(|s| s.terminal_font_family.as_ref())
+        .or_else(|| setting(|s| s.editor_font_family.as_ref()));
+
+    let (font, detected, config_path, confidence) = match font_name {
+        Some(((font, detected), config_path)) => (
+            font,
+            detected,
+            Some(config_path.clone()),
+            Confidence::Certain,
+        ),
+        None => {
+            // The default font follows the platform of the window, which is
+            // Windows for a WSL session.
+            let font = if wsl { "Consolas" } else { DEFAULT_FONT };
+            let config_path = layers.first().map(|layer| layer.path.clone());
+            (font.to_string(), false, config_path, Confidence::Probable)
         }
-        Err(reason) => return config_error(Terminal::Vscode, reason, Some(config_path)),
-    };
-
-    let font_name = settings
-        .terminal_font_family
-        .as_deref()
-        .or(settings.editor_font_family.as_deref());
-
-    match font_name {
-        Some(name) => {
-            let font = normalize_font_name(name);
-            DetectionResult {
-                detected: Some(is_nerd_font(&font)),
-                source: DetectionSource::TerminalConfig,
-                terminal: Some(Terminal::Vscode),
-                font: Some(font),
-                config_path: Some(config_path),
-                profile: None,
-                error_reason: None,
-                confidence: Confidence::Certain,
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Vscode),
+        font: Some(font),
+        config_path,
+        profile: profile_name,
+        variant,
+        error_reason: None,
+        confidence,
+    }
+}
+
+/// Collects the settings files that apply, most specific first: the nearest
+/// folder's `.vscode/settings.json`, then the `settings` of the nearest
+/// `.code-workspace` file, then the user settings of the active profile,
+/// which are the default profile's unless it has its own. Project files are
+/// looked up from `cwd` to `home`.
+fn settings_layers(
+    cwd: Option<&Path>,
+    home: Option<&Path>,
+    user_dir: &Path,
+    profile: Option<Profile>,
+) -> Result<Vec<SettingsLayer>, (String, PathBuf)> {
+    let mut layers = Vec::new();
+
+    let folder = ancestor_dirs(cwd, home).find(|dir| dir.join(".vscode/settings.json").is_file());
+    let folder_settings = match &folder {
+        Some(dir) => read_layer(dir.join(".vscode/settings.json"))?,
+        None => None,
+    };
+
+    // Which workspace is open can't be known; the nearest file is the best
+    // guess, and the first by name if a directory has several.
+    let workspace_path = ancestor_dirs(cwd, home).find_map(|dir| {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == "code-workspace") && path.is_file()
+            })
+            .collect();
+        files.sort();
+        files.into_iter().next()
+    });
+    let workspace = match workspace_path {
+        Some(path) => match read_json5_settings::<CodeWorkspace>(&path) {
+            Ok(workspace) => workspace.map(|workspace| (path, workspace)),
+            Err(reason) => return Err((reason, path)),
+        },
+        None => None,
+    };
+
+    // In a workspace window, folder settings only come from the workspace's
+    // own folders.
+    let in_workspace = |dir: &Path| {
+        workspace
+            .as_ref()
+            .is_none_or(|(path, workspace)| workspace.contains(path, dir))
+    };
+    if let (Some(dir), Some(layer)) = (folder, folder_settings)
+        && in_workspace(&dir)
+    {
+        layers.push(layer);
+    }
+    if let Some((path, workspace)) = workspace
+        && let Some(settings) = workspace.settings
+    {
+        layers.push(SettingsLayer { path, settings });
+    }
+
+    // A profile with its own settings doesn't inherit the font settings of
+    // the default profile.
+    let user_path = profile
+        .and_then(|profile| profile.settings_path)
+        .unwrap_or_else(|| user_dir.join("settings.json"));
+    layers.extend(read_layer(user_path)?);
+    Ok(layers)
+}
+
+/// Reads the settings file at `path`, if there is one.
+fn read_layer(path: PathBuf) -> Result<Option<SettingsLayer>, (String, PathBuf)> {
+    match read_json5_settings::<VscodeSettings>(&path) {
+        Ok(settings) => Ok(settings.map(|settings| SettingsLayer { path, settings })),
+        Err(reason) => Err((reason, path)),
+    }
+}
+
+/// VS Code's record of profiles and which workspace uses which, from
+/// `User/globalStorage/storage.json`.
+#[derive(Deserialize)]
+struct ProfileStorage {
+    #[serde(default, rename = "userDataProfiles")]
+    profiles: Vec<StoredProfile>,
+    #[serde(default, rename = "profileAssociations")]
+    associations: Option<ProfileAssociations>,
+}
+
+#[derive(Deserialize)]
+struct StoredProfile {
+    /// Directory under `User/profiles/` holding the profile's settings.
+    location: String,
+    name: String,
+    #[serde(default, rename = "useDefaultFlags")]
+    use_default: Option<UseDefaultFlags>,
+}
+
+#[derive(Deserialize)]
+struct UseDefaultFlags {
+    #[serde(default)]
+    settings: bool,
+}
+
+#[derive(Deserialize)]
+struct ProfileAssociations {
+    /// Workspace URIs mapped to profile locations.
+    #[serde(default)]
+    workspaces: HashMap<String, String>,
+}
+
+/// A non-default profile that applies to the session.
+struct Profile {
+    name: String,
+    /// The profile's own `settings.json`, unless it shares the default
+    /// profile's settings.
+    settings_path: Option<PathBuf>,
+}
+
+/// Finds the profile associated with the workspace that contains `cwd`,
+/// preferring the innermost workspace. Returns `None` for the default
+/// profile.
+fn active_profile(
+    user_dir: &Path,
+    cwd: Option<&Path>,
+) -> Result<Option<Profile>, (String, PathBuf)> {
+    let storage_path = user_dir.join("globalStorage/storage.json");
+    let storage = match read_json5_settings::<ProfileStorage>(&storage_path) {
+        Ok(Some(storage)) => storage,
+        Ok(None) => return Ok(None),
+        Err(reason) => return Err((reason, storage_path)),
+    };
+    let (Some(cwd), Some(associations)) = (cwd, storage.associations) else {
+        return Ok(None);
+    };
+
+    let location = associations
+        .workspaces
+        .iter()
+        .filter_map(|(uri, location)| {
+            let path = uri_path(uri)?;
+            // A `.code-workspace` file covers the directory it's in.
+            let root = if path.extension().is_some_and(|ext| ext == "code-workspace") {
+                path.parent()?.to_path_buf()
+            } else {
+                path
+            };
+            cwd.starts_with(&root).then_some((root, location))
+        })
+        .max_by_key(|(root, _)| root.components().count())
+        .map(|(_, location)| location);
+
+    Ok(location.and_then(|location| {
+        let profile = storage
+            .profiles
+            .into_iter()
+            .find(|profile| &profile.location == location)?;
+        let shares_settings = profile.use_default.is_some_and(|flags| flags.settings);
+        Some(Profile {
+            settings_path: (!shares_settings).then(|| {
+                user_dir
+                    .join("profiles")
+                    .join(&profile.location)
+                    .join("settings.json")
+            }),
+            name: profile.name,
+        })
+    }))
+}
+
+/// Returns the path of a `file://` URI, or of a `vscode-remote://` URI such
+/// as `vscode-remote://wsl+Ubuntu/home/me/app`.
+fn uri_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri
+        .strip_prefix("file://")
+        .or_else(|| uri.strip_prefix("vscode-remote://"))?;
+    let path = &rest[rest.find('/')?..];
+    Some(PathBuf::from(percent_decode(path)))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| value.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
             }
         }
-        None => config_error(
-            Terminal::Vscode,
-            "no font configured".to_string(),
-            Some(config_path),
-        ),
     }
+    String::from_utf8_lossy(&decoded).into_owned()
 }
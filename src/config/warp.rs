@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_os = "macos"))]
+use serde::Deserialize;
+
+use super::{config_error, var};
+#[cfg(not(target_os = "macos"))]
+use super::{config_home, read_json5_settings};
+use crate::font::{is_nerd_font, normalize_font_name};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Warp's built-in font, used until another is picked.
+const DEFAULT_FONT: &str = "Hack";
+
+#[cfg(not(target_os = "macos"))]
+#[derive(Deserialize)]
+struct UserPreferences {
+    #[serde(default)]
+    prefs: Option<Preferences>,
+}
+
+#[cfg(not(target_os = "macos"))]
+#[derive(Deserialize)]
+struct Preferences {
+    #[serde(default, rename = "FontName")]
+    font_name: Option<String>,
+}
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Warp, "HOME is not set".to_string(), None),
+    };
+
+    // Preview builds keep separate settings, and say so in their version,
+    // e.g. `v0.2024.10.29.08.02.preview_00`.
+    let preview = var(vars, "TERM_PROGRAM_VERSION").is_some_and(|v| v.contains("preview"));
+
+    let (font_name, config_path) = match read_font_name(vars, home, preview) {
+        Ok(found) => found,
+        Err((reason, path)) => return config_error(Terminal::Warp, reason, Some(path)),
+    };
+
+    // Preferences hold JSON-encoded values, so a font name is quoted.
+    let font_name = font_name.map(|name| serde_json::from_str::<String>(&name).unwrap_or(name));
+    let (font, confidence) = match font_name.filter(|name| !name.trim().is_empty()) {
+        Some(name) => (normalize_font_name(&name), Confidence::Certain),
+        None => (DEFAULT_FONT.to_string(), Confidence::Probable),
+    };
+
+    DetectionResult {
+        detected: Some(is_nerd_font(&font)),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Warp),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
+
+/// Reads `FontName` from Warp's preferences, returning it along with the
+/// preferences file if one exists.
+#[cfg(target_os = "macos")]
+fn read_font_name(
+    _vars: &[(String, String)],
+    home: &Path,
+    preview: bool,
+) -> Result<(Option<String>, Option<PathBuf>), (String, PathBuf)> {
+    let domain = if preview {
+        "dev.warp.Warp-Preview"
+    } else {
+        "dev.warp.Warp-Stable"
+    };
+    let path = home.join(format!("Library/Preferences/{domain}.plist"));
+    if !path.exists() {
+        return Ok((None, None));
+    }
+
+    let root =
+        crate::plist::load_root_dictionary(&path).map_err(|reason| (reason, path.clone()))?;
+    let font_name = root
+        .get("FontName")
+        .and_then(plist::Value::as_string)
+        .map(ToString::to_string);
+    Ok((font_name, Some(path)))
+}
+
+/// Reads `FontName` from Warp's preferences, returning it along with the
+/// preferences file if one exists.
+#[cfg(not(target_os = "macos"))]
+fn read_font_name(
+    vars: &[(String, String)],
+    home: &Path,
+    preview: bool,
+) -> Result<(Option<String>, Option<PathBuf>), (String, PathBuf)> {
+    let dir = if preview {
+        "warp-terminal-preview"
+    } else {
+        "warp-terminal"
+    };
+    let path = config_home(vars, home)
+        .join(dir)
+        .join("user_preferences.json");
+
+    match read_json5_settings::<UserPreferences>(&path) {
+        Ok(Some(preferences)) => {
+            let font_name = preferences.prefs.and_then(|prefs| prefs.font_name);
+            Ok((font_name, Some(path)))
+        }
+        Ok(None) => Ok((None, None)),
+        Err(reason) => Err((reason, path)),
+    }
+}
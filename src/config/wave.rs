@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{config_error, read_json5_settings, var};
+use crate::font::{css_font_families, match_font_list};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Wave ships Hack Nerd Font Mono and registers it as `Hack`, its default
+/// terminal font.
+const BUNDLED_FAMILY: &str = "Hack";
+const BUNDLED_FONT: &str = "Hack Nerd Font Mono";
+
+#[derive(Deserialize)]
+struct WaveSettings {
+    #[serde(default, rename = "term:fontfamily")]
+    font_family: Option<String>,
+}
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return config_error(Terminal::Wave, "HOME is not set".to_string(), None),
+    };
+
+    // `WAVETERM_CONFIG_HOME` overrides the config directory, and Wave's
+    // shells inherit it.
+    let config_dir = var(vars, "WAVETERM_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config/waveterm"));
+    let config_path = config_dir.join("settings.json");
+
+    let (font_family, config_path) = match read_json5_settings::<WaveSettings>(&config_path) {
+        Ok(Some(settings)) => (settings.font_family, Some(config_path)),
+        Ok(None) => (None, None),
+        Err(reason) => return config_error(Terminal::Wave, reason, Some(config_path)),
+    };
+
+    let (font_family, confidence) = match font_family.filter(|v| !v.trim().is_empty()) {
+        Some(font_family) => (font_family, Confidence::Certain),
+        None => (BUNDLED_FAMILY.to_string(), Confidence::Probable),
+    };
+
+    // The value is a CSS font-family list, so later entries cover glyphs
+    // missing from earlier ones.
+    let families: Vec<String> = css_font_families(&font_family)
+        .into_iter()
+        .map(|family| {
+            if family.eq_ignore_ascii_case(BUNDLED_FAMILY) {
+                BUNDLED_FONT.to_string()
+            } else {
+                family
+            }
+        })
+        .collect();
+    let Some((font, detected)) = match_font_list(&families) else {
+        return config_error(
+            Terminal::Wave,
+            "no font configured".to_string(),
+            config_path,
+        );
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Wave),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::wsl::{is_wsl, windows_home};
+use super::{config_error, read_json5_settings, var};
+use crate::font::{match_font_list, normalize_font_name};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Font used by profiles that don't set one.
+const DEFAULT_FONT: &str = "Cascadia Mono";
+
+/// Settings locations relative to the Windows user profile, in the order they
+/// are tried: the Store package, the Preview package, then unpackaged installs
+/// (e.g. via Scoop or a portable zip).
+const SETTINGS_PATHS: &[&str] = &[
+    "AppData/Local/Packages/Microsoft.WindowsTerminal_8wekyb3d8bbwe/LocalState/settings.json",
+    "AppData/Local/Packages/Microsoft.WindowsTerminalPreview_8wekyb3d8bbwe/LocalState/settings.json",
+    "AppData/Local/Microsoft/Windows Terminal/settings.json",
+];
+
+#[derive(Deserialize)]
+struct WindowsTerminalSettings {
+    #[serde(default, rename = "defaultProfile")]
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: Option<Profiles>,
+}
+
+/// `profiles` is an object with `defaults` and `list` in current versions,
+/// and a bare list of profiles in older ones.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Profiles {
+    Object {
+        #[serde(default)]
+        defaults: Option<Profile>,
+        #[serde(default)]
+        list: Vec<Profile>,
+    },
+    List(Vec<Profile>),
+}
+
+#[derive(Deserialize)]
+struct Profile {
+    #[serde(default)]
+    guid: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    font: Option<ProfileFont>,
+    /// Pre-1.10 spelling of `font.face`.
+    #[serde(default, rename = "fontFace")]
+    font_face: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProfileFont {
+    #[serde(default)]
+    face: Option<String>,
+}
+
+impl Profile {
+    fn face(&self) -> Option<&str> {
+        self.font
+            .as_ref()
+            .and_then(|font| font.face.as_deref())
+            .or(self.font_face.as_deref())
+            .filter(|face| !face.trim().is_empty())
+    }
+
+    /// Profiles are referenced by GUID, or by name in older settings.
+    fn is(&self, id: &str) -> bool {
+        let id = id.trim();
+        self.guid
+            .as_deref()
+            .is_some_and(|guid| guid.eq_ignore_ascii_case(id))
+            || self.name.as_deref() == Some(id)
+    }
+}
+
+pub fn resolve(vars: &[(String, String)], windows_root: &Path) -> DetectionResult {
+    if !is_wsl(vars) {
+        return config_error(
+            Terminal::WindowsTerminal,
+            "Windows Terminal settings are only reachable from WSL".to_string(),
+            None,
+        );
+    }
+
+    let home = match windows_home(vars, windows_root) {
+        Ok(home) => home,
+        Err(reason) => return config_error(Terminal::WindowsTerminal, reason, None),
+    };
+
+    for candidate in SETTINGS_PATHS.iter().map(|path| home.join(path)) {
+        match read_json5_settings::<WindowsTerminalSettings>(&candidate) {
+            Ok(Some(settings)) => return resolve_from_settings(vars, settings, candidate),
+            Ok(None) => continue,
+            Err(reason) => {
+                return config_error(Terminal::WindowsTerminal, reason, Some(candidate));
+            }
+        }
+    }
+
+    config_error(
+        Terminal::WindowsTerminal,
+        "no settings file found".to_string(),
+        None,
+    )
+}
+
+fn resolve_from_settings(
+    vars: &[(String, String)],
+    settings: WindowsTerminalSettings,
+    config_path: PathBuf,
+) -> DetectionResult {
+    let (defaults, list) = match settings.profiles {
+        Some(Profiles::Object { defaults, list }) => (defaults, list),
+        Some(Profiles::List(list)) => (None, list),
+        None => (None, Vec::new()),
+    };
+
+    // WT_PROFILE_ID names the profile this tab was opened with.
+    let profile_id = var(vars, "WT_PROFILE_ID")
+        .filter(|v| !v.is_empty())
+        .or(settings.default_profile.as_deref());
+    let profile = profile_id.and_then(|id| list.iter().find(|profile| profile.is(id)));
+
+    let configured = profile
+        .and_then(Profile::face)
+        .or_else(|| defaults.as_ref().and_then(Profile::face));
+    let (face, confidence) = match configured {
+        Some(face) => (face, Confidence::Certain),
+        None => (DEFAULT_FONT, Confidence::Probable),
+    };
+
+    // Since 1.17, `face` may list fallback fonts separated by commas.
+    let families: Vec<String> = face
+        .split(',')
+        .map(normalize_font_name)
+        .filter(|family| !family.is_empty())
+        .collect();
+    let Some((font, detected)) = match_font_list(&families) else {
+        return config_error(
+            Terminal::WindowsTerminal,
+            "no font configured".to_string(),
+            Some(config_path),
+        );
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::WindowsTerminal),
+        font: Some(font),
+        config_path: Some(config_path),
+        profile: profile.and_then(|profile| profile.name.clone()),
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+
+use super::var;
+
+/// Profile directories under `C:\Users` that don't belong to a person.
+const SYSTEM_PROFILES: &[&str] = &["All Users", "Default", "Default User", "Public"];
+
+/// Returns `true` when running inside WSL, where Windows-side settings are
+/// reachable through the mounted system drive.
+pub fn is_wsl(vars: &[(String, String)]) -> bool {
+    var(vars, "WSL_DISTRO_NAME").is_some_and(|v| !v.is_empty())
+}
+
+/// Finds the Windows user profile directory under `<root>/Users`.
+///
+/// `USERPROFILE` is used when it was shared through `WSLENV`. Otherwise the
+/// only real profile is taken, or the one named like the Linux user when there
+/// are several.
+pub fn windows_home(vars: &[(String, String)], root: &Path) -> Result<PathBuf, String> {
+    let users = root.join("Users");
+
+    if let Some(name) = var(vars, "USERPROFILE")
+        .and_then(|profile| profile.rsplit(['\\', '/']).next())
+        .filter(|name| !name.is_empty())
+    {
+        return Ok(users.join(name));
+    }
+
+    let entries = std::fs::read_dir(&users)
+        .map_err(|e| format!("failed to list Windows users in {}: {e}", users.display()))?;
+    let profiles: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("AppData").is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| !SYSTEM_PROFILES.contains(&name.as_str()))
+        .collect();
+
+    let chosen = match profiles.as_slice() {
+        [only] => Some(only),
+        _ => var(vars, "USER").and_then(|user| {
+            profiles
+                .iter()
+                .find(|profile| profile.eq_ignore_ascii_case(user))
+        }),
+    };
+
+    chosen.map(|name| users.join(name)).ok_or_else(|| {
+        format!(
+            "could not determine the Windows user profile in {}",
+            users.display()
+        )
+    })
+}
@@ -0,0 +1,95 @@
+use std::path::{Path, PathBuf};
+
+use super::xfconf::Channel;
+use super::{config_error, config_home, var};
+use crate::font::{match_font_list, pango_families};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// Where xfconfd stores each channel, relative to the XDG config home.
+const CHANNEL_DIR: &str = "xfce4/xfconf/xfce-perchannel-xml";
+
+/// Font description xfce4-terminal uses when `/font-name` is unset.
+const DEFAULT_FONT: &str = "Monospace 12";
+/// xfsettingsd's default for `/Gtk/MonospaceFontName`.
+const SYSTEM_DEFAULT_FONT: &str = "Monospace 10";
+
+pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => {
+            return config_error(Terminal::Xfce4Terminal, "HOME is not set".to_string(), None);
+        }
+    };
+    let channel_dir = config_home(vars, home).join(CHANNEL_DIR);
+
+    let path = channel_dir.join("xfce4-terminal.xml");
+    let channel = match Channel::read(&path) {
+        Ok(channel) => channel,
+        Err(reason) => return config_error(Terminal::Xfce4Terminal, reason, Some(path)),
+    };
+
+    // These were FontUseSystem and FontName back when settings lived in
+    // terminalrc; the xfconf migration renamed them.
+    let use_system_font = channel
+        .as_ref()
+        .and_then(|channel| channel.get_bool("/font-use-system"))
+        .unwrap_or(false);
+    if use_system_font {
+        return system_font(&channel_dir.join("xsettings.xml"));
+    }
+
+    match channel
+        .as_ref()
+        .and_then(|channel| channel.get("/font-name"))
+    {
+        Some(description) => resolve_description(description, Some(path), Confidence::Certain),
+        None => resolve_description(DEFAULT_FONT, channel.map(|_| path), Confidence::Probable),
+    }
+}
+
+/// With "Use system font" ticked, the font comes from the XFCE settings
+/// daemon's `MonospaceFontName`.
+fn system_font(path: &Path) -> DetectionResult {
+    let channel = match Channel::read(path) {
+        Ok(channel) => channel,
+        Err(reason) => {
+            return config_error(Terminal::Xfce4Terminal, reason, Some(path.to_path_buf()));
+        }
+    };
+
+    match channel
+        .as_ref()
+        .and_then(|channel| channel.get("/Gtk/MonospaceFontName"))
+    {
+        Some(description) => {
+            resolve_description(description, Some(path.to_path_buf()), Confidence::Certain)
+        }
+        None => resolve_description(SYSTEM_DEFAULT_FONT, None, Confidence::Probable),
+    }
+}
+
+fn resolve_description(
+    description: &str,
+    config_path: Option<PathBuf>,
+    confidence: Confidence,
+) -> DetectionResult {
+    let Some((font, detected)) = match_font_list(&pango_families(description)) else {
+        return config_error(
+            Terminal::Xfce4Terminal,
+            "no font configured".to_string(),
+            config_path,
+        );
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(Terminal::Xfce4Terminal),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence,
+    }
+}
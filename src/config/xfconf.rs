@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::read_settings_file;
+use super::xml::{self, Tag};
+
+/// An xfconf channel, as stored in
+/// `~/.config/xfce4/xfconf/xfce-perchannel-xml/<channel>.xml`.
+///
+/// Properties nest, so values are keyed by their full path, e.g.
+/// `/Gtk/MonospaceFontName`.
+pub struct Channel {
+    values: HashMap<String, String>,
+}
+
+impl Channel {
+    /// Reads an xfconf channel file. Returns:
+    /// - `Ok(Some(channel))` if the file exists and was parsed successfully
+    /// - `Ok(None)` if the file does not exist or is inaccessible
+    /// - `Err(reason)` if the file exists but is malformed
+    pub fn read(path: &Path) -> Result<Option<Self>, String> {
+        let Some(content) = read_settings_file(path)? else {
+            return Ok(None);
+        };
+        parse(&content)
+            .map(|values| Some(Self { values }))
+            .map_err(|reason| format!("failed to parse {}: {reason}", path.display()))
+    }
+
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.values.get(path).map(String::as_str)
+    }
+
+    pub fn get_bool(&self, path: &str) -> Option<bool> {
+        match self.get(path)? {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Collects the `value` of every `<property>` element.
+fn parse(content: &str) -> Result<HashMap<String, String>, String> {
+    let mut values = HashMap::new();
+    let mut path: Vec<String> = Vec::new();
+    let mut seen_channel = false;
+
+    for tag in xml::tags(content)? {
+        match tag {
+            Tag::Open { name, .. } if name == "channel" => seen_channel = true,
+            Tag::Open {
+                name,
+                attributes,
+                self_closing,
+            } if name == "property" => {
+                let name = attributes
+                    .get("name")
+                    .ok_or("property without a name")?
+                    .clone();
+                let full_path = path
+                    .iter()
+                    .chain(std::iter::once(&name))
+                    .fold(String::new(), |acc, segment| acc + "/" + segment);
+                if let Some(value) = attributes.get("value") {
+                    values.insert(full_path, value.clone());
+                }
+                if !self_closing {
+                    path.push(name);
+                }
+            }
+            Tag::Close { name } if name == "property" => {
+                path.pop().ok_or("unexpected </property>")?;
+            }
+            _ => {}
+        }
+    }
+
+    if !seen_channel {
+        return Err("missing <channel> element".to_string());
+    }
+    if !path.is_empty() {
+        return Err("unclosed <property> element".to_string());
+    }
+    Ok(values)
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+/// A tag of an XML document, as returned by [`tags`].
+pub enum Tag {
+    Open {
+        name: String,
+        attributes: HashMap<String, String>,
+        /// Whether the tag closes itself (`<option ... />`).
+        self_closing: bool,
+    },
+    Close {
+        name: String,
+    },
+}
+
+/// Splits an XML document into its tags, skipping text, comments, the XML
+/// declaration and doctypes. Only the subset of XML that settings files use
+/// is understood; CDATA sections and character references are not.
+pub fn tags(content: &str) -> Result<Vec<Tag>, String> {
+    let mut tags = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or("unterminated comment")?;
+            rest = &after[end + 3..];
+            continue;
+        }
+
+        let end = rest.find('>').ok_or("unterminated tag")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            tags.push(Tag::Close {
+                name: name.trim().to_string(),
+            });
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+        tags.push(Tag::Open {
+            name: name.to_string(),
+            attributes: parse_attributes(attributes)?,
+            self_closing,
+        });
+    }
+
+    Ok(tags)
+}
+
+fn parse_attributes(mut rest: &str) -> Result<HashMap<String, String>, String> {
+    let mut attributes = HashMap::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(attributes);
+        }
+
+        let (name, after) = rest.split_once('=').ok_or("attribute without a value")?;
+        let after = after.trim_start();
+        let quote = after
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '"' | '\''))
+            .ok_or("unquoted attribute value")?;
+        let value_end = after[1..]
+            .find(quote)
+            .ok_or("unterminated attribute value")?;
+        attributes.insert(name.trim().to_string(), unescape(&after[1..=value_end]));
+        rest = &after[value_end + 2..];
+    }
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,199 @@
+use std::path::{Path, PathBuf};
+
+use super::read_settings_file;
+
+/// How deep `#include` directives are followed.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// X resources loaded from files such as `~/.Xresources`, as `xrdb` would
+/// merge them.
+///
+/// `#include` is followed; other preprocessor directives are ignored, so
+/// every branch of an `#ifdef` is read.
+pub struct Resources {
+    entries: Vec<Resource>,
+}
+
+struct Resource {
+    components: Vec<(Binding, String)>,
+    value: String,
+    path: PathBuf,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Binding {
+    /// `.`: the component is at the next level.
+    Tight,
+    /// `*`: any number of levels may come first.
+    Loose,
+}
+
+/// One level of a resource lookup, e.g. the widget `vt100` of class `VT100`.
+pub struct Level<'a> {
+    pub name: &'a str,
+    /// Classes to match, most preferred first.
+    pub classes: &'a [&'a str],
+}
+
+impl Resources {
+    /// Reads `paths` in order; resources from later files override earlier
+    /// ones. Missing files are skipped. Returns `Ok(None)` if none exist.
+    pub fn load(paths: &[PathBuf]) -> Result<Option<Self>, (String, PathBuf)> {
+        let mut entries = Vec::new();
+        let mut found = false;
+        for path in paths {
+            found |= read_file(path, 0, &mut entries)?;
+        }
+        Ok(found.then_some(Self { entries }))
+    }
+
+    /// Looks up the resource at `levels` with the Xrm precedence rules,
+    /// returning its value and the file it came from.
+    pub fn get(&self, levels: &[Level]) -> Option<(&str, &Path)> {
+        let mut best: Option<(Vec<(u8, u8)>, &Resource)> = None;
+        for entry in &self.entries {
+            let components: Vec<_> = entry
+                .components
+                .iter()
+                .map(|(binding, name)| (*binding, name.as_str()))
+                .collect();
+            let Some(score) = precedence(&components, levels) else {
+                continue;
+            };
+            // Later definitions win ties, as when xrdb merges a file.
+            if best.as_ref().is_none_or(|(best, _)| score >= *best) {
+                best = Some((score, entry));
+            }
+        }
+        best.map(|(_, entry)| (entry.value.as_str(), entry.path.as_path()))
+    }
+}
+
+/// Scores how `components` match `levels`, one `(match, binding)` pair per
+/// level, so that the lexicographically greatest score is the entry Xlib would
+/// pick: a matched level beats a skipped one, a name beats a class beats `?`,
+/// and a tight binding beats a loose one. Earlier classes of a level beat
+/// later ones.
+fn precedence(components: &[(Binding, &str)], levels: &[Level]) -> Option<Vec<(u8, u8)>> {
+    let Some(((binding, component), rest)) = components.split_first() else {
+        return levels.is_empty().then(Vec::new);
+    };
+    let (level, deeper) = levels.split_first()?;
+
+    let kind = if *component == level.name {
+        Some(u8::MAX)
+    } else if let Some(index) = level.classes.iter().position(|class| class == component) {
+        Some(u8::MAX - 1 - index as u8)
+    } else if *component == "?" {
+        Some(1)
+    } else {
+        None
+    };
+    let matched = kind.and_then(|kind| {
+        let mut score = vec![(kind, u8::from(*binding == Binding::Tight))];
+        score.extend(precedence(rest, deeper)?);
+        Some(score)
+    });
+    let skipped = (*binding == Binding::Loose)
+        .then(|| {
+            let mut score = vec![(0, 0)];
+            score.extend(precedence(components, deeper)?);
+            Some(score)
+        })
+        .flatten();
+
+    matched.into_iter().chain(skipped).max()
+}
+
+/// Appends the resources in `path` to `entries`. Returns whether the file
+/// exists.
+fn read_file(
+    path: &Path,
+    depth: usize,
+    entries: &mut Vec<Resource>,
+) -> Result<bool, (String, PathBuf)> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err((
+            format!("includes nested too deeply in {}", path.display()),
+            path.to_path_buf(),
+        ));
+    }
+    let Some(content) = read_settings_file(path).map_err(|reason| (reason, path.to_path_buf()))?
+    else {
+        return Ok(false);
+    };
+
+    for line in logical_lines(&content) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('!') {
+            continue;
+        }
+
+        if let Some(directive) = line.strip_prefix('#') {
+            if let Some(include) = directive.trim_start().strip_prefix("include") {
+                let include = include.trim().trim_matches(['"', '<', '>']);
+                let dir = path.parent().unwrap_or(Path::new("."));
+                read_file(&dir.join(include), depth + 1, entries)?;
+            }
+            continue;
+        }
+
+        let Some((spec, value)) = line.split_once(':') else {
+            continue;
+        };
+        let components = parse_spec(spec.trim());
+        if !components.is_empty() {
+            entries.push(Resource {
+                components,
+                value: value.trim().to_string(),
+                path: path.to_path_buf(),
+            });
+        }
+    }
+    Ok(true)
+}
+
+/// Joins lines ending in a backslash with the next one.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        match line.strip_suffix('\\') {
+            Some(continued) => current.push_str(continued),
+            None => {
+                current.push_str(line);
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Splits a resource specification such as `XTerm*vt100.faceName` into
+/// components and the bindings that precede them.
+fn parse_spec(spec: &str) -> Vec<(Binding, String)> {
+    let mut components = Vec::new();
+    let mut binding = Binding::Tight;
+    let mut name = String::new();
+    for c in spec.chars() {
+        match c {
+            '.' | '*' => {
+                if !name.is_empty() {
+                    components.push((binding, std::mem::take(&mut name)));
+                    binding = Binding::Tight;
+                }
+                if c == '*' {
+                    binding = Binding::Loose;
+                }
+            }
+            _ => name.push(c),
+        }
+    }
+    if !name.is_empty() {
+        components.push((binding, name));
+    }
+    components
+}
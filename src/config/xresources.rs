@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+
+use super::xrdb::{Level, Resources};
+use super::{config_error, var};
+use crate::font::{
+    fontconfig_family, match_font_list, normalize_font_name, x_font_families, xlfd_family,
+};
+use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
+
+/// xterm's core font when neither `faceName` nor `font` is set.
+const XTERM_DEFAULT_FONT: &str = "fixed";
+/// urxvt's compiled-in font list starts with the fontconfig `monospace` alias.
+const URXVT_DEFAULT_FONT: &str = "Monospace";
+
+pub fn resolve_xterm(vars: &[(String, String)]) -> DetectionResult {
+    let resources = match load(vars) {
+        Ok(resources) => resources,
+        Err((reason, path)) => return config_error(Terminal::Xterm, reason, path),
+    };
+
+    let vt100 = |resource| {
+        [
+            Level {
+                name: "xterm",
+                classes: &["XTerm"],
+            },
+            Level {
+                name: "vt100",
+                classes: &["VT100"],
+            },
+            resource,
+        ]
+    };
+
+    // faceName selects a FreeType font and takes precedence over the core
+    // font. fontconfig falls back through the pattern's families in order.
+    let face_name = Level {
+        name: "faceName",
+        classes: &["FaceName"],
+    };
+    if let Some((pattern, path)) = resources.as_ref().and_then(|r| r.get(&vt100(face_name))) {
+        let pattern = pattern.strip_prefix("xft:").unwrap_or(pattern);
+        let families: Vec<String> = pattern
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(fontconfig_family)
+            .filter(|family| !family.is_empty())
+            .collect();
+        return font_list_result(Terminal::Xterm, &families, path);
+    }
+
+    let font = Level {
+        name: "font",
+        classes: &["Font"],
+    };
+    if let Some((font, path)) = resources.as_ref().and_then(|r| r.get(&vt100(font))) {
+        let family = if font.starts_with('-') {
+            xlfd_family(font)
+        } else {
+            Some(normalize_font_name(font))
+        };
+        return font_list_result(Terminal::Xterm, &Vec::from_iter(family), path);
+    }
+
+    default_font(Terminal::Xterm, XTERM_DEFAULT_FONT)
+}
+
+pub fn resolve_urxvt(vars: &[(String, String)]) -> DetectionResult {
+    let resources = match load(vars) {
+        Ok(resources) => resources,
+        Err((reason, path)) => return config_error(Terminal::Urxvt, reason, path),
+    };
+
+    // urxvt also honours resources of the older Rxvt class.
+    let levels = [
+        Level {
+            name: "urxvt",
+            classes: &["URxvt", "Rxvt"],
+        },
+        Level {
+            name: "font",
+            classes: &["Font"],
+        },
+    ];
+    match resources.as_ref().and_then(|r| r.get(&levels)) {
+        // Glyphs missing from the first font are taken from later entries.
+        Some((spec, path)) => font_list_result(Terminal::Urxvt, &x_font_families(spec), path),
+        None => default_font(Terminal::Urxvt, URXVT_DEFAULT_FONT),
+    }
+}
+
+/// Loads `~/.Xdefaults` and then `~/.Xresources`, so that resources loaded
+/// with `xrdb` win over the older file.
+fn load(vars: &[(String, String)]) -> Result<Option<Resources>, (String, Option<PathBuf>)> {
+    let home = match var(vars, "HOME") {
+        Some(value) if !value.is_empty() => Path::new(value),
+        _ => return Err(("HOME is not set".to_string(), None)),
+    };
+
+    let paths = [home.join(".Xdefaults"), home.join(".Xresources")];
+    Resources::load(&paths).map_err(|(reason, path)| (reason, Some(path)))
+}
+
+fn font_list_result(terminal: Terminal, families: &[String], path: &Path) -> DetectionResult {
+    let config_path = Some(PathBuf::from(path));
+    let Some((font, detected)) = match_font_list(families) else {
+        return config_error(terminal, "empty font list".to_string(), config_path);
+    };
+
+    DetectionResult {
+        detected: Some(detected),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(terminal),
+        font: Some(font),
+        config_path,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence: Confidence::Certain,
+    }
+}
+
+/// Without font resources the terminal uses its built-in default, which is
+/// not a Nerd Font. System app-defaults can change it, hence `Probable`.
+fn default_font(terminal: Terminal, font: &str) -> DetectionResult {
+    DetectionResult {
+        detected: Some(false),
+        source: DetectionSource::TerminalConfig,
+        terminal: Some(terminal),
+        font: Some(font.to_string()),
+        config_path: None,
+        profile: None,
+        variant: None,
+        error_reason: None,
+        confidence: Confidence::Probable,
+    }
+}
@@ -0,0 +1,223 @@
+/// The block mappings of a YAML document, as used by Tabby and Contour.
+///
+/// Only what settings files need is understood: nested `key: value` mappings
+/// and quoted or plain scalars. Sequences, block scalars, flow collections,
+/// anchors and multi-line plain scalars are skipped rather than rejected, so
+/// their keys can't be looked up.
+pub struct Yaml {
+    root: Vec<(String, Node)>,
+}
+
+enum Node {
+    Scalar(String),
+    Mapping(Vec<(String, Node)>),
+    /// A value this reader doesn't interpret, or null.
+    Other,
+}
+
+struct Line<'a> {
+    number: usize,
+    indent: usize,
+    text: &'a str,
+}
+
+impl Yaml {
+    /// Parses `content`, returning the 1-based number of the first line that
+    /// isn't understood on failure.
+    pub fn parse(content: &str) -> Result<Self, usize> {
+        let mut lines = Vec::new();
+        for (index, raw) in content.lines().enumerate() {
+            let number = index + 1;
+            let text = strip_comment(raw).ok_or(number)?.trim_end();
+            let trimmed = text.trim_start_matches(' ');
+            if trimmed.is_empty()
+                || trimmed == "---"
+                || trimmed == "..."
+                || (trimmed.starts_with('%') && text.len() == trimmed.len())
+            {
+                continue;
+            }
+            if trimmed.starts_with('\t') {
+                return Err(number);
+            }
+            lines.push(Line {
+                number,
+                indent: text.len() - trimmed.len(),
+                text: trimmed,
+            });
+        }
+
+        let mut position = 0;
+        let indent = lines.first().map_or(0, |line| line.indent);
+        let root = parse_mapping(&lines, &mut position, indent)?;
+        match lines.get(position) {
+            Some(line) => Err(line.number),
+            None => Ok(Self { root }),
+        }
+    }
+
+    /// Returns the scalar at `path`, e.g. `["terminal", "font"]`.
+    pub fn get(&self, path: &[&str]) -> Option<&str> {
+        let (last, parents) = path.split_last()?;
+        let mut mapping = &self.root;
+        for key in parents {
+            match lookup(mapping, key)? {
+                Node::Mapping(entries) => mapping = entries,
+                _ => return None,
+            }
+        }
+        match lookup(mapping, last)? {
+            Node::Scalar(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Later keys win, as most YAML loaders let duplicates override.
+fn lookup<'a>(mapping: &'a [(String, Node)], key: &str) -> Option<&'a Node> {
+    mapping
+        .iter()
+        .rev()
+        .find(|(name, _)| name == key)
+        .map(|(_, node)| node)
+}
+
+/// Parses the mapping whose keys start at `indent`.
+fn parse_mapping(
+    lines: &[Line],
+    position: &mut usize,
+    indent: usize,
+) -> Result<Vec<(String, Node)>, usize> {
+    let mut entries = Vec::new();
+    while let Some(line) = lines.get(*position) {
+        if line.indent < indent {
+            break;
+        }
+        if line.indent > indent {
+            return Err(line.number);
+        }
+        *position += 1;
+
+        if is_sequence_item(line.text) {
+            skip_children(lines, position, indent, true);
+            continue;
+        }
+
+        let (key, value) = split_entry(line.text).ok_or(line.number)?;
+        let node = if value.is_empty() {
+            match lines.get(*position) {
+                Some(next) if next.indent > indent && !is_sequence_item(next.text) => {
+                    Node::Mapping(parse_mapping(lines, position, next.indent)?)
+                }
+                _ => {
+                    skip_children(lines, position, indent, true);
+                    Node::Other
+                }
+            }
+        } else {
+            skip_children(lines, position, indent, false);
+            scalar(value).ok_or(line.number)?
+        };
+        entries.push((key, node));
+    }
+    Ok(entries)
+}
+
+/// Skips the lines nested under the line before `position`. Sequence items
+/// may sit at the parent's own indentation.
+fn skip_children(lines: &[Line], position: &mut usize, indent: usize, sequence: bool) {
+    while let Some(line) = lines.get(*position) {
+        if line.indent > indent
+            || (sequence && line.indent == indent && is_sequence_item(line.text))
+        {
+            *position += 1;
+        } else {
+            break;
+        }
+    }
+}
+
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Splits `key: value` at the first colon followed by a space or the end of
+/// the line, outside quotes.
+fn split_entry(text: &str) -> Option<(String, &str)> {
+    let mut quote = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') if index == 0 => quote = Some(c),
+            (None, ':') if chars.peek().is_none_or(|(_, next)| *next == ' ') => {
+                let key = match scalar(text[..index].trim_end())? {
+                    Node::Scalar(key) => key,
+                    _ => return None,
+                };
+                return Some((key, text[index + 1..].trim()));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Interprets a single-line value. Values this reader doesn't understand
+/// become [`Node::Other`]; an unterminated quote is an error.
+fn scalar(value: &str) -> Option<Node> {
+    match value.chars().next() {
+        Some('"') => {
+            let inner = value[1..].strip_suffix('"')?;
+            let mut unescaped = String::new();
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    unescaped.push(match chars.next()? {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                } else {
+                    unescaped.push(c);
+                }
+            }
+            Some(Node::Scalar(unescaped))
+        }
+        Some('\'') => {
+            let inner = value[1..].strip_suffix('\'')?;
+            Some(Node::Scalar(inner.replace("''", "'")))
+        }
+        Some('|' | '>' | '[' | '{' | '&' | '*' | '!') => Some(Node::Other),
+        _ if value == "~" || value == "null" => Some(Node::Other),
+        _ => Some(Node::Scalar(value.to_string())),
+    }
+}
+
+/// Drops a `#` comment, which starts a line or follows whitespace outside
+/// quotes. Returns `None` for an unterminated quoted scalar.
+fn strip_comment(line: &str) -> Option<&str> {
+    let mut quote = None;
+    let mut previous = ' ';
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            // Quotes only open a scalar at the start of a key or value.
+            None if (c == '"' || c == '\'') && matches!(previous, ' ' | ':' | '-') => {
+                quote = Some(c);
+            }
+            // `''` escapes a quote inside a single-quoted scalar.
+            None if c == '\'' && previous == '\'' => {
+                quote = Some(c);
+            }
+            None if c == '#' && previous == ' ' => return Some(&line[..index]),
+            None => {}
+        }
+        previous = c;
+    }
+    quote.is_none().then_some(line)
+}
@@ -1,8 +1,8 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
-use super::{config_error, read_json5_settings, var};
+use super::{ancestor_dirs, config_error, read_json5_settings, var};
 use crate::font::{is_nerd_font, normalize_font_name};
 use crate::{Confidence, DetectionResult, DetectionSource, Terminal};
 
@@ -20,36 +20,50 @@ struct ZedTerminal {
     font_family: Option<String>,
 }
 
-pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
+pub fn resolve(vars: &[(String, String)], cwd: Option<&Path>) -> DetectionResult {
     let home = match var(vars, "HOME") {
         Some(value) if !value.is_empty() => value,
         _ => return config_error(Terminal::Zed, "HOME is not set".to_string(), None),
     };
 
-    let config_path = Path::new(home).join(".config/zed/settings.json");
+    // Project settings in `.zed/settings.json` override user settings, and a
+    // nested project directory's settings override its parent's.
+    let paths = ancestor_dirs(cwd, Some(Path::new(home)))
+        .map(|dir| dir.join(".zed/settings.json"))
+        .chain([Path::new(home).join(".config/zed/settings.json")]);
 
-    let settings = match read_json5_settings::<ZedSettings>(&config_path) {
-        Ok(Some(s)) => s,
-        Ok(None) => return config_error(Terminal::Zed, "no settings file found".to_string(), None),
-        Err(reason) => return config_error(Terminal::Zed, reason, Some(config_path)),
+    let mut layers: Vec<(PathBuf, ZedSettings)> = Vec::new();
+    for path in paths {
+        match read_json5_settings::<ZedSettings>(&path) {
+            Ok(Some(settings)) => layers.push((path, settings)),
+            Ok(None) => {}
+            Err(reason) => return config_error(Terminal::Zed, reason, Some(path)),
+        }
+    }
+    let Some((nearest, _)) = layers.first() else {
+        return config_error(Terminal::Zed, "no settings file found".to_string(), None);
     };
 
-    let font_name = settings
-        .terminal
-        .as_ref()
-        .and_then(|t| t.font_family.as_deref())
-        .or(settings.buffer_font_family.as_deref());
+    // Each key comes from the most specific layer that sets it.
+    let setting = |key: fn(&ZedSettings) -> Option<&String>| {
+        layers
+            .iter()
+            .find_map(|(path, settings)| Some((key(settings)?, path)))
+    };
+    let font_name = setting(|s| s.terminal.as_ref()?.font_family.as_ref())
+        .or_else(|| setting(|s| s.buffer_font_family.as_ref()));
 
     match font_name {
-        Some(name) => {
+        Some((name, config_path)) => {
             let font = normalize_font_name(name);
             DetectionResult {
                 detected: Some(is_nerd_font(&font)),
                 source: DetectionSource::TerminalConfig,
                 terminal: Some(Terminal::Zed),
                 font: Some(font),
-                config_path: Some(config_path),
+                config_path: Some(config_path.clone()),
                 profile: None,
+                variant: None,
                 error_reason: None,
                 confidence: Confidence::Certain,
             }
@@ -57,7 +71,7 @@ pub fn resolve(vars: &[(String, String)]) -> DetectionResult {
         None => config_error(
             Terminal::Zed,
             "no font configured".to_string(),
-            Some(config_path),
+            Some(nearest.clone()),
         ),
     }
 }
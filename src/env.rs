@@ -5,7 +5,17 @@ pub enum EnvDecision {
 }
 
 pub fn detect(vars: &[(String, String)]) -> EnvDecision {
-    match crate::var(vars, "NERD_FONT") {
+    detect_key(vars, "NERD_FONT")
+}
+
+/// Reads the answer a client forwarded over SSH (see `has-nerd-font export`).
+/// `LC_*` variables are accepted by most sshd configs out of the box.
+pub fn detect_forwarded(vars: &[(String, String)]) -> EnvDecision {
+    detect_key(vars, "LC_NERD_FONT")
+}
+
+fn detect_key(vars: &[(String, String)], key: &str) -> EnvDecision {
+    match crate::var(vars, key) {
         Some(raw) => parse_nerd_font(raw),
         None => EnvDecision::Continue,
     }
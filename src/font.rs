@@ -25,6 +25,172 @@ fn has_nf_token(s: &str, token: &str) -> bool {
     sep_before.iter().any(|p| s.contains(p)) || sep_after.iter().any(|p| s.contains(p))
 }
 
+/// Extracts the family from a Qt font string as written by `QFont::toString`,
+/// e.g. `Hack Nerd Font Mono,10,-1,5,50,0,0,0,0,0`.
+pub fn qt_font_family(font: &str) -> Option<String> {
+    let family = normalize_font_name(font.split(',').next()?);
+    (!family.is_empty()).then_some(family)
+}
+
+/// Words Pango accepts after the family list for style, variant, weight,
+/// stretch and gravity.
+const PANGO_STYLE_WORDS: &[&str] = &[
+    "normal",
+    "roman",
+    "oblique",
+    "italic",
+    "small-caps",
+    "all-small-caps",
+    "petite-caps",
+    "all-petite-caps",
+    "unicase",
+    "title-caps",
+    "thin",
+    "ultra-light",
+    "extra-light",
+    "light",
+    "semi-light",
+    "demi-light",
+    "book",
+    "regular",
+    "medium",
+    "semi-bold",
+    "demi-bold",
+    "bold",
+    "ultra-bold",
+    "extra-bold",
+    "heavy",
+    "black",
+    "ultra-heavy",
+    "extra-heavy",
+    "ultra-condensed",
+    "extra-condensed",
+    "condensed",
+    "semi-condensed",
+    "semi-expanded",
+    "expanded",
+    "extra-expanded",
+    "ultra-expanded",
+    "not-rotated",
+    "south",
+    "upside-down",
+    "north",
+    "rotated-left",
+    "east",
+    "rotated-right",
+    "west",
+];
+
+/// Splits a Pango font description such as `JetBrainsMono Nerd Font Bold 12`
+/// or `Hack, Symbols Nerd Font 11` into family names. The trailing size,
+/// variations (`@wght=500`) and style words are dropped.
+pub fn pango_families(description: &str) -> Vec<String> {
+    let mut words: Vec<&str> = description.split_whitespace().collect();
+
+    if words.last().is_some_and(|word| word.starts_with('@')) {
+        words.pop();
+    }
+    if words.last().is_some_and(|word| {
+        let size = word.strip_suffix("px").unwrap_or(word);
+        size.parse::<f64>().is_ok()
+    }) {
+        words.pop();
+    }
+    while words.len() > 1
+        && words
+            .last()
+            .is_some_and(|word| PANGO_STYLE_WORDS.contains(&word.to_ascii_lowercase().as_str()))
+    {
+        words.pop();
+    }
+
+    words
+        .join(" ")
+        .split(',')
+        .map(normalize_font_name)
+        .filter(|family| !family.is_empty())
+        .collect()
+}
+
+/// Picks the font that decides the answer from a fallback list: the first
+/// Nerd Font anywhere in the list (since the terminal renders missing glyphs
+/// from later entries), otherwise the primary font.
+pub fn match_font_list(families: &[String]) -> Option<(String, bool)> {
+    families
+        .iter()
+        .find(|family| is_nerd_font(family))
+        .map(|family| (family.clone(), true))
+        .or_else(|| families.first().map(|family| (family.clone(), false)))
+}
+
+/// Extracts the family from a fontconfig pattern such as
+/// `Hack Nerd Font Mono:size=11` or `Hack-11`. When the pattern lists several
+/// families (`Hack,Symbols Nerd Font`), only the first is returned.
+pub fn fontconfig_family(pattern: &str) -> String {
+    let families = pattern.split(':').next().unwrap_or_default();
+    let family = families.split(',').next().unwrap_or_default().trim();
+
+    // A trailing `-<size>` is shorthand for the size property.
+    match family.rsplit_once('-') {
+        Some((name, size)) if !size.is_empty() && size.parse::<f64>().is_ok() => {
+            normalize_font_name(name)
+        }
+        _ => normalize_font_name(family),
+    }
+}
+
+/// Extracts the family from an X Logical Font Description such as
+/// `-misc-fixed-medium-r-normal--13-120-75-75-c-70-iso10646-1`.
+pub fn xlfd_family(xlfd: &str) -> Option<String> {
+    let family = xlfd.strip_prefix('-')?.split('-').nth(1)?;
+    (!family.is_empty() && family != "*").then(|| normalize_font_name(family))
+}
+
+/// Splits an X resource font list (as used by xterm and urxvt) into family
+/// names. Entries are comma-separated and each is either `xft:<pattern>`,
+/// an XLFD, or a core font alias. The order of the list is preserved, since
+/// later entries are fallbacks for glyphs missing from earlier ones.
+pub fn x_font_families(spec: &str) -> Vec<String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter_map(|entry| {
+            if let Some(pattern) = entry.strip_prefix("xft:") {
+                Some(fontconfig_family(pattern))
+            } else if entry.starts_with('-') {
+                xlfd_family(entry)
+            } else {
+                Some(normalize_font_name(entry))
+            }
+        })
+        .filter(|family| !family.is_empty())
+        .collect()
+}
+
+/// Splits a CSS `font-family` list such as `'Fira Code', "Symbols Nerd Font",
+/// monospace` into family names, with quotes removed.
+pub fn css_font_families(list: &str) -> Vec<String> {
+    let mut families = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+
+    for c in list.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => families.push(std::mem::take(&mut current)),
+            (None, c) => current.push(c),
+        }
+    }
+    families.push(current);
+
+    families
+        .iter()
+        .map(|family| normalize_font_name(family))
+        .filter(|family| !family.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +364,135 @@ mod tests {
     fn leading_trailing_whitespace() {
         assert!(is_nerd_font("  JetBrainsMono Nerd Font  "));
     }
+
+    // --- X font specifications ---
+
+    #[test]
+    fn fontconfig_family_with_properties() {
+        assert_eq!(
+            fontconfig_family("Hack Nerd Font Mono:size=11"),
+            "Hack Nerd Font Mono"
+        );
+    }
+
+    #[test]
+    fn fontconfig_family_with_size_suffix() {
+        assert_eq!(fontconfig_family("DejaVu Sans Mono-11"), "DejaVu Sans Mono");
+    }
+
+    #[test]
+    fn fontconfig_family_keeps_hyphenated_name() {
+        assert_eq!(fontconfig_family("Fira-Code:pixelsize=14"), "Fira-Code");
+    }
+
+    #[test]
+    fn xlfd_family_from_full_name() {
+        assert_eq!(
+            xlfd_family("-misc-fixed-medium-r-normal--13-120-75-75-c-70-iso10646-1").as_deref(),
+            Some("fixed")
+        );
+    }
+
+    #[test]
+    fn xlfd_family_wildcard() {
+        assert_eq!(xlfd_family("-*-*-medium-r-*-*-14-*"), None);
+    }
+
+    #[test]
+    fn x_font_families_xft_fallback_list() {
+        assert_eq!(
+            x_font_families("xft:Hack:size=11,xft:Symbols Nerd Font Mono:size=11"),
+            ["Hack", "Symbols Nerd Font Mono"]
+        );
+    }
+
+    #[test]
+    fn x_font_families_mixed_list() {
+        assert_eq!(
+            x_font_families("xft:Iosevka-12, -misc-fixed-medium-r-normal--13-*-*-*-*-*-iso10646-1"),
+            ["Iosevka", "fixed"]
+        );
+    }
+
+    #[test]
+    fn x_font_families_core_alias() {
+        assert_eq!(x_font_families("9x15"), ["9x15"]);
+    }
+
+    #[test]
+    fn qt_font_family_qt5() {
+        assert_eq!(
+            qt_font_family("Hack Nerd Font Mono,10,-1,5,50,0,0,0,0,0").as_deref(),
+            Some("Hack Nerd Font Mono")
+        );
+    }
+
+    #[test]
+    fn qt_font_family_qt6_with_style_name() {
+        assert_eq!(
+            qt_font_family("JetBrainsMono NF,11,-1,5,400,0,0,0,0,0,0,0,0,0,0,1,Regular").as_deref(),
+            Some("JetBrainsMono NF")
+        );
+    }
+
+    #[test]
+    fn pango_families_strips_style_and_size() {
+        assert_eq!(
+            pango_families("JetBrainsMono Nerd Font Mono Bold Italic 12"),
+            ["JetBrainsMono Nerd Font Mono"]
+        );
+    }
+
+    #[test]
+    fn pango_families_family_list() {
+        assert_eq!(
+            pango_families("Hack, Symbols Nerd Font 11"),
+            ["Hack", "Symbols Nerd Font"]
+        );
+    }
+
+    #[test]
+    fn pango_families_pixel_size_and_variations() {
+        assert_eq!(
+            pango_families("Iosevka Term Semi-Bold 14px @wght=550"),
+            ["Iosevka Term"]
+        );
+    }
+
+    #[test]
+    fn pango_families_family_only() {
+        assert_eq!(pango_families("Monospace"), ["Monospace"]);
+    }
+
+    #[test]
+    fn css_font_families_quoted_list() {
+        assert_eq!(
+            css_font_families(r#"Menlo, "DejaVu Sans Mono", 'Hack, Nerd Font', monospace"#),
+            ["Menlo", "DejaVu Sans Mono", "Hack, Nerd Font", "monospace"]
+        );
+    }
+
+    #[test]
+    fn css_font_families_empty_entries() {
+        assert_eq!(css_font_families(" , Fira Code,, "), ["Fira Code"]);
+        assert!(css_font_families("").is_empty());
+    }
+
+    #[test]
+    fn match_font_list_prefers_nerd_fallback() {
+        let families = ["Iosevka".to_string(), "Symbols Nerd Font".to_string()];
+        assert_eq!(
+            match_font_list(&families),
+            Some(("Symbols Nerd Font".to_string(), true))
+        );
+    }
+
+    #[test]
+    fn match_font_list_reports_primary_font() {
+        let families = ["Iosevka".to_string(), "DejaVu Sans Mono".to_string()];
+        assert_eq!(
+            match_font_list(&families),
+            Some(("Iosevka".to_string(), false))
+        );
+    }
 }
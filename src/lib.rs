@@ -1,14 +1,24 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
 
+use std::borrow::Cow;
+
 mod config;
 mod env;
 mod font;
+mod multiplexer;
+mod options;
 #[cfg(target_os = "macos")]
 mod plist;
+mod probe;
+mod procfs;
+mod query;
+mod remote_hints;
 mod terminal;
+mod tty;
 mod types;
 
+pub use options::DetectOptions;
 pub use types::{Confidence, DetectionResult, DetectionSource, Terminal};
 
 /// Look up the last occurrence of `key` in the env var list (last wins).
@@ -42,21 +52,108 @@ enum LayerOutcome<T> {
 /// }
 /// ```
 pub fn detect(vars: &[(String, String)]) -> DetectionResult {
+    detect_with(vars, &DetectOptions::default())
+}
+
+/// Remembers `detected` as the answer for the SSH client this session comes
+/// from. Later detections in sessions from the same client return it instead of
+/// [`DetectionSource::RemoteSession`]. Returns the path of the store, under
+/// `$XDG_STATE_HOME/has-nerd-font/`.
+///
+/// Fails when the environment has no SSH client address or the store can't be
+/// written.
+pub fn remember(vars: &[(String, String)], detected: bool) -> std::io::Result<std::path::PathBuf> {
+    remote_hints::remember(vars, detected)
+}
+
+/// Like [`detect`], but with [`DetectOptions`] controlling which additional
+/// sources are consulted.
+pub fn detect_with(vars: &[(String, String)], options: &DetectOptions) -> DetectionResult {
+    let procfs = procfs::ProcFs::new(options.proc_root());
+    let pid = options.pid().or_else(|| procfs.self_pid());
+    let context = Context {
+        options,
+        procfs,
+        pid,
+    };
+    let vars = process_env_layer(vars, &context);
+    let vars = vars.as_ref();
+
+    let mut result = cascade(vars, &context);
+    if result.detected.is_none() && options.terminal_query() {
+        result = font_query_layer(result);
+    }
+    if result.detected.is_none() && options.glyph_probe() {
+        result = probe_layer(result);
+    }
+    result
+}
+
+/// State shared by the detection layers.
+struct Context<'a> {
+    options: &'a DetectOptions,
+    procfs: procfs::ProcFs<'a>,
+    /// The process detection runs as: our own, or the one given by `--pid`.
+    pid: Option<u32>,
+}
+
+fn cascade(vars: &[(String, String)], context: &Context) -> DetectionResult {
     if let LayerOutcome::Final(result) = env_layer(vars) {
         return result;
     }
 
-    let terminal = match terminal_layer(vars) {
+    let client = multiplexer_layer(vars, context);
+    let vars = client
+        .as_ref()
+        .map_or(vars, |client| client.vars.as_slice());
+
+    if let LayerOutcome::Final(result) = remote_hint_layer(vars) {
+        return result;
+    }
+
+    let terminal = match terminal_layer(vars, context, client.as_ref()) {
         LayerOutcome::Final(result) => return result,
         LayerOutcome::Continue(terminal) => terminal,
     };
 
     match ssh_gate_layer(vars, terminal) {
         LayerOutcome::Final(result) => result,
-        LayerOutcome::Continue(terminal) => config::resolve(terminal, vars),
+        LayerOutcome::Continue(terminal) => config::resolve(terminal, vars, context),
     }
 }
 
+/// Builds the variable list the cascade runs against: the target process's
+/// environment when `--pid` is given, preceded by ancestor environments when
+/// enabled. Since [`var`] takes the last occurrence, ancestors are ordered
+/// farthest first so that nearer processes (and finally our own) win.
+fn process_env_layer<'a>(
+    vars: &'a [(String, String)],
+    context: &Context,
+) -> Cow<'a, [(String, String)]> {
+    let Context {
+        options, procfs, ..
+    } = context;
+
+    let own = match options.pid().and_then(|pid| procfs.environ(pid)) {
+        Some(environ) => Cow::Owned(environ),
+        None => Cow::Borrowed(vars),
+    };
+
+    let Some(pid) = context.pid.filter(|_| options.ancestor_env()) else {
+        return own;
+    };
+
+    let mut environs: Vec<_> = procfs
+        .same_user_ancestors(pid)
+        .filter_map(|process| procfs.environ(process.pid))
+        .collect();
+    environs.reverse();
+
+    let mut merged: Vec<_> = environs.into_iter().flatten().collect();
+    merged.extend(own.iter().cloned());
+    Cow::Owned(merged)
+}
+
 fn env_layer(vars: &[(String, String)]) -> LayerOutcome<()> {
     match env::detect(vars) {
         env::EnvDecision::OverrideEnabled => {
@@ -71,30 +168,172 @@ fn env_layer(vars: &[(String, String)]) -> LayerOutcome<()> {
     }
 }
 
-fn terminal_layer(vars: &[(String, String)]) -> LayerOutcome<Terminal> {
-    match terminal::detect(vars) {
+/// In a remote session, the client may have forwarded its own answer (see
+/// `has-nerd-font export`), which beats anything that can be found out on
+/// this side of the connection.
+fn remote_hint_layer(vars: &[(String, String)]) -> LayerOutcome<()> {
+    if !is_remote_session(vars) {
+        return LayerOutcome::Continue(());
+    }
+
+    match env::detect_forwarded(vars) {
+        env::EnvDecision::OverrideEnabled => {
+            LayerOutcome::Final(base_result(Some(true), DetectionSource::ForwardedEnv, None))
+        }
+        env::EnvDecision::OverrideDisabled => LayerOutcome::Final(base_result(
+            Some(false),
+            DetectionSource::ForwardedEnv,
+            None,
+        )),
+        env::EnvDecision::Continue => LayerOutcome::Continue(()),
+    }
+}
+
+/// Inside tmux, screen or Zellij, the env vars describe the multiplexer rather
+/// than the terminal. Ask the multiplexer about its attached client so the
+/// rest of the cascade runs against the outer terminal.
+fn multiplexer_layer(vars: &[(String, String)], context: &Context) -> Option<multiplexer::Client> {
+    let multiplexer = multiplexer::detect(vars)?;
+    Some(multiplexer::attached_client(
+        multiplexer,
+        vars,
+        &context.procfs,
+        context.pid,
+    ))
+}
+
+fn terminal_layer(
+    vars: &[(String, String)],
+    context: &Context,
+    client: Option<&multiplexer::Client>,
+) -> LayerOutcome<Option<Terminal>> {
+    let decision = match terminal::detect(vars) {
+        terminal::TerminalDecision::Unknown => identify_without_env(context, client),
+        // VTE_VERSION only names the widget library; the emulator binary may
+        // say which VTE terminal this is.
+        terminal::TerminalDecision::Identified(Terminal::Vte) => {
+            match identify_from_process_tree(context, client) {
+                terminal::TerminalDecision::Unknown => {
+                    terminal::TerminalDecision::Identified(Terminal::Vte)
+                }
+                decision => decision,
+            }
+        }
+        decision => decision,
+    };
+
+    match decision {
         terminal::TerminalDecision::Bundled(terminal) => LayerOutcome::Final(base_result(
             Some(true),
             DetectionSource::BundledTerminal,
             Some(terminal),
         )),
-        terminal::TerminalDecision::Identified(terminal) => LayerOutcome::Continue(terminal),
-        terminal::TerminalDecision::Unknown => {
-            LayerOutcome::Final(base_result(None, DetectionSource::UnknownTerminal, None))
+        terminal::TerminalDecision::Identified(terminal) => LayerOutcome::Continue(Some(terminal)),
+        terminal::TerminalDecision::Unknown => LayerOutcome::Continue(None),
+    }
+}
+
+/// When no env var identifies the terminal (e.g. under `sudo`, `env -i`, or
+/// over SSH), use what a multiplexer knows about its client, then look for a
+/// known emulator among the client's or our ancestors, then ask the terminal
+/// itself if enabled.
+fn identify_without_env(
+    context: &Context,
+    client: Option<&multiplexer::Client>,
+) -> terminal::TerminalDecision {
+    if let Some(termtype) = client.and_then(|client| client.termtype.clone()) {
+        return terminal::detect_from_name(termtype);
+    }
+
+    let decision = identify_from_process_tree(context, client);
+    if !matches!(decision, terminal::TerminalDecision::Unknown) {
+        return decision;
+    }
+
+    if context.options.terminal_query()
+        && let Ok(mut tty) = tty::DevTty::open()
+    {
+        return terminal::detect_from_query(&mut tty);
+    }
+
+    terminal::TerminalDecision::Unknown
+}
+
+/// Looks for a known emulator among the multiplexer client's ancestors, then
+/// our own.
+fn identify_from_process_tree(
+    context: &Context,
+    client: Option<&multiplexer::Client>,
+) -> terminal::TerminalDecision {
+    let client_pid = client.and_then(|client| client.pid);
+    for pid in [client_pid, context.pid].into_iter().flatten() {
+        let decision = terminal::detect_from_process_tree(&context.procfs, pid);
+        if !matches!(decision, terminal::TerminalDecision::Unknown) {
+            return decision;
         }
     }
+    terminal::TerminalDecision::Unknown
 }
 
-fn ssh_gate_layer(vars: &[(String, String)], terminal: Terminal) -> LayerOutcome<Terminal> {
+/// Over SSH the client's config isn't reachable, so only an answer remembered
+/// for this client can decide. The terminal may still be unknown here, since a
+/// remembered answer doesn't depend on it.
+fn ssh_gate_layer(vars: &[(String, String)], terminal: Option<Terminal>) -> LayerOutcome<Terminal> {
     if is_remote_session(vars) {
-        return LayerOutcome::Final(base_result(
-            None,
-            DetectionSource::RemoteSession,
-            Some(terminal),
-        ));
+        return LayerOutcome::Final(match remote_hints::lookup(vars) {
+            Some(stored) => DetectionResult {
+                config_path: Some(stored.path),
+                ..base_result(
+                    Some(stored.detected),
+                    DetectionSource::StoredAnswer,
+                    terminal,
+                )
+            },
+            None => DetectionResult {
+                variant: config::remote_kind(terminal.as_ref(), vars).map(ToString::to_string),
+                ..base_result(None, DetectionSource::RemoteSession, terminal)
+            },
+        });
+    }
+
+    match terminal {
+        Some(terminal) => LayerOutcome::Continue(terminal),
+        None => LayerOutcome::Final(base_result(None, DetectionSource::UnknownTerminal, None)),
+    }
+}
+
+/// Falls back to asking the terminal for its font with OSC 50 when the
+/// cascade could not decide. Keeps the inconclusive result if the terminal
+/// doesn't reply.
+fn font_query_layer(result: DetectionResult) -> DetectionResult {
+    let families = tty::DevTty::open()
+        .map(|mut tty| query::font_families(&mut tty))
+        .unwrap_or_default();
+
+    match font::match_font_list(&families) {
+        Some((font, detected)) => DetectionResult {
+            font: Some(font),
+            ..base_result(Some(detected), DetectionSource::FontQuery, result.terminal)
+        },
+        None => result,
     }
+}
 
-    LayerOutcome::Continue(terminal)
+/// Falls back to measuring how the terminal renders Nerd Font glyphs when the
+/// cascade could not decide. Keeps the inconclusive result if the terminal
+/// doesn't report cursor positions.
+fn probe_layer(result: DetectionResult) -> DetectionResult {
+    let measured = tty::DevTty::open()
+        .ok()
+        .and_then(|mut tty| probe::probe(&mut tty));
+
+    match measured {
+        Some(detected) => DetectionResult {
+            confidence: Confidence::Probable,
+            ..base_result(Some(detected), DetectionSource::GlyphProbe, result.terminal)
+        },
+        None => result,
+    }
 }
 
 fn base_result(
@@ -109,6 +348,7 @@ fn base_result(
         font: None,
         config_path: None,
         profile: None,
+        variant: None,
         error_reason: None,
         confidence: Confidence::Certain,
     }
@@ -1,5 +1,5 @@
-use clap::Parser;
-use has_nerd_font::{DetectionResult, DetectionSource, detect};
+use clap::{Parser, Subcommand, ValueEnum};
+use has_nerd_font::{DetectOptions, DetectionResult, DetectionSource, detect_with};
 
 #[derive(Debug, Parser)]
 #[command(version)]
@@ -8,12 +8,57 @@ struct Cli {
     json: bool,
     #[arg(long)]
     explain: bool,
+    #[arg(long)]
+    ancestor_env: bool,
+    #[arg(long)]
+    query_terminal: bool,
+    #[arg(long)]
+    probe: bool,
+    #[arg(long, value_name = "PID")]
+    pid: Option<u32>,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print ~/.ssh/config lines that forward this terminal's answer to remote hosts
+    Export,
+    /// Remember the answer for the SSH client this session comes from
+    Remember { answer: Answer },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Answer {
+    Yes,
+    No,
 }
 
 fn main() {
     let cli = Cli::parse();
     let env_vars: Vec<(String, String)> = std::env::vars().collect();
-    let result = detect(&env_vars);
+
+    if let Some(Command::Remember { answer }) = cli.command {
+        if let Err(e) = has_nerd_font::remember(&env_vars, answer == Answer::Yes) {
+            eprintln!("has-nerd-font: cannot remember answer: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut options = DetectOptions::default()
+        .with_ancestor_env(cli.ancestor_env)
+        .with_terminal_query(cli.query_terminal)
+        .with_glyph_probe(cli.probe);
+    if let Some(pid) = cli.pid {
+        options = options.with_pid(pid);
+    }
+    let result = detect_with(&env_vars, &options);
+
+    if let Some(Command::Export) = cli.command {
+        print!("{}", ssh_config(&result));
+        return;
+    }
 
     if cli.json {
         println!(
@@ -41,6 +86,14 @@ fn exit_code(result: &DetectionResult) -> i32 {
         (DetectionSource::TerminalConfig, Some(true)) => 0,
         (DetectionSource::TerminalConfig, Some(false)) => 6,
         (DetectionSource::TerminalConfig, None) => 5,
+        (DetectionSource::FontQuery, Some(true)) => 0,
+        (DetectionSource::FontQuery, _) => 6,
+        (DetectionSource::GlyphProbe, Some(true)) => 0,
+        (DetectionSource::GlyphProbe, _) => 6,
+        (DetectionSource::ForwardedEnv, Some(true)) => 0,
+        (DetectionSource::ForwardedEnv, _) => 1,
+        (DetectionSource::StoredAnswer, Some(true)) => 0,
+        (DetectionSource::StoredAnswer, _) => 1,
         _ => 1,
     }
 }
@@ -54,9 +107,12 @@ fn explain(result: &DetectionResult) -> String {
         DetectionSource::UnknownTerminal => {
             "cannot determine terminal; terminal is unknown".to_string()
         }
-        DetectionSource::RemoteSession => {
-            "running in remote session; local terminal config not inspected".to_string()
-        }
+        DetectionSource::RemoteSession => match &result.variant {
+            Some(kind) => {
+                format!("running in remote session ({kind}); local terminal config not inspected")
+            }
+            None => "running in remote session; local terminal config not inspected".to_string(),
+        },
         DetectionSource::NoResolver => "known terminal has no resolver implemented yet".to_string(),
         DetectionSource::ConfigError => format!(
             "failed to read terminal configuration: {}",
@@ -74,6 +130,51 @@ fn explain(result: &DetectionResult) -> String {
                 "terminal configuration status is unknown".to_string()
             }
         }
+        DetectionSource::FontQuery => {
+            if result.detected == Some(true) {
+                "terminal reports a Nerd Font as its active font".to_string()
+            } else {
+                "terminal reports an active font that is not a Nerd Font".to_string()
+            }
+        }
+        DetectionSource::GlyphProbe => {
+            if result.detected == Some(true) {
+                "terminal advanced the cursor differently for Nerd Font glyphs than for a missing glyph"
+                    .to_string()
+            } else {
+                "terminal dropped Nerd Font glyphs".to_string()
+            }
+        }
+        DetectionSource::ForwardedEnv => {
+            if result.detected == Some(true) {
+                "detected Nerd Font from LC_NERD_FONT forwarded by the SSH client".to_string()
+            } else {
+                "Nerd Font disabled by LC_NERD_FONT forwarded by the SSH client".to_string()
+            }
+        }
+        DetectionSource::StoredAnswer => format!(
+            "using the answer remembered for this SSH client: {}",
+            if result.detected == Some(true) {
+                "Nerd Font available"
+            } else {
+                "no Nerd Font"
+            }
+        ),
         _ => "unknown detection source".to_string(),
     }
 }
+
+/// Renders a `Host *` block for ~/.ssh/config. `LC_TERMINAL` is forwarded
+/// as-is, and the local answer travels as `LC_NERD_FONT`; `LC_*` variables are
+/// accepted by the stock sshd config on most distributions.
+fn ssh_config(result: &DetectionResult) -> String {
+    let mut lines = vec![
+        "Host *".to_string(),
+        "    SendEnv LC_TERMINAL LC_TERMINAL_VERSION".to_string(),
+    ];
+    match result.detected {
+        Some(detected) => lines.push(format!("    SetEnv LC_NERD_FONT={}", u8::from(detected))),
+        None => lines.insert(0, format!("# LC_NERD_FONT not set: {}", explain(result))),
+    }
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
@@ -0,0 +1,274 @@
+use std::process::{Command, Stdio};
+
+use crate::procfs::{ProcFs, Process};
+use crate::var;
+
+/// A terminal multiplexer running between us and the terminal emulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+    Zellij,
+}
+
+impl Multiplexer {
+    /// Recognizes a multiplexer by the name it answers XTVERSION or DA2 with.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Tmux, Self::Screen, Self::Zellij]
+            .into_iter()
+            .find(|multiplexer| multiplexer.process_name() == name)
+    }
+
+    /// Process name of the multiplexer's client and server.
+    fn process_name(self) -> &'static str {
+        match self {
+            Self::Tmux => "tmux",
+            Self::Screen => "screen",
+            Self::Zellij => "zellij",
+        }
+    }
+}
+
+/// Env vars set by multiplexers inside their panes. They describe the
+/// multiplexer rather than the outer terminal, so they are dropped before
+/// identifying it.
+const MARKER_VARS: &[&str] = &[
+    "TMUX",
+    "TMUX_PANE",
+    "STY",
+    "WINDOW",
+    "ZELLIJ",
+    "ZELLIJ_PANE_ID",
+    "ZELLIJ_SESSION_NAME",
+    "TERM",
+];
+
+/// What the multiplexer knows about the terminal its client is attached to.
+pub struct Client {
+    /// Environment to run the cascade against for the outer terminal.
+    pub vars: Vec<(String, String)>,
+    /// Terminal name from the client's XTVERSION reply (tmux's
+    /// `client_termtype`), e.g. `kitty`.
+    pub termtype: Option<String>,
+    /// PID of the attached client process, for walking the process tree.
+    pub pid: Option<u32>,
+}
+
+pub fn detect(vars: &[(String, String)]) -> Option<Multiplexer> {
+    let var = |key| crate::var(vars, key).filter(|v| !v.is_empty());
+
+    if var("TMUX").is_some()
+        || var("TERM_PROGRAM").is_some_and(|v| v.trim().eq_ignore_ascii_case("tmux"))
+    {
+        Some(Multiplexer::Tmux)
+    } else if var("ZELLIJ").is_some() {
+        Some(Multiplexer::Zellij)
+    } else if var("STY").is_some() {
+        Some(Multiplexer::Screen)
+    } else {
+        None
+    }
+}
+
+/// Asks the multiplexer about its attached client.
+///
+/// The client's own environment (read from procfs) is the most faithful
+/// picture of the outer terminal. When it is not readable, tmux's session
+/// environment and `client_termname` are layered over our own variables
+/// instead.
+pub fn attached_client(
+    multiplexer: Multiplexer,
+    vars: &[(String, String)],
+    procfs: &ProcFs,
+    pid: Option<u32>,
+) -> Client {
+    // tmux also claims TERM_PROGRAM (and TERM_PROGRAM_VERSION) for itself.
+    let tmux_program = var(vars, "TERM_PROGRAM").is_some_and(|v| v.eq_ignore_ascii_case("tmux"));
+    let mut outer: Vec<_> = vars
+        .iter()
+        .filter(|(key, _)| !MARKER_VARS.contains(&key.as_str()))
+        .filter(|(key, _)| !(tmux_program && key.starts_with("TERM_PROGRAM")))
+        .cloned()
+        .collect();
+
+    let (client_pid, termname, termtype) = match multiplexer {
+        Multiplexer::Tmux => {
+            let info = tmux_client_info(vars);
+            outer.extend(tmux_environment(vars));
+            info
+        }
+        Multiplexer::Screen | Multiplexer::Zellij => {
+            (find_client(multiplexer, vars, procfs, pid), None, None)
+        }
+    };
+
+    match client_pid.and_then(|client| procfs.environ(client)) {
+        Some(environ) => outer = environ,
+        None => outer.extend(termname.map(|termname| ("TERM".to_string(), termname))),
+    }
+
+    Client {
+        vars: outer,
+        termtype,
+        pid: client_pid,
+    }
+}
+
+/// Runs `tmux display-message` for the most recently active client, returning
+/// its PID, `TERM` and XTVERSION-derived terminal name.
+fn tmux_client_info(vars: &[(String, String)]) -> (Option<u32>, Option<String>, Option<String>) {
+    let Some(output) = tmux(
+        vars,
+        &[
+            "display-message",
+            "-p",
+            "#{client_pid}\t#{client_termname}\t#{client_termtype}",
+        ],
+    ) else {
+        return (None, None, None);
+    };
+
+    let mut fields = output.lines().next().unwrap_or_default().split('\t');
+    let non_empty = |field: Option<&str>| {
+        field
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(ToString::to_string)
+    };
+    let pid = fields.next().and_then(|pid| pid.trim().parse().ok());
+    let termname = non_empty(fields.next());
+    let termtype = non_empty(fields.next()).and_then(|t| crate::query::version_string_name(&t));
+    (pid, termname, termtype)
+}
+
+/// Runs `tmux show-environment`, which reflects the `update-environment`
+/// variables of the most recently attached client. Lines of the form `-NAME`
+/// mark variables removed from the session and are skipped.
+fn tmux_environment(vars: &[(String, String)]) -> Vec<(String, String)> {
+    tmux(vars, &["show-environment"])
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.starts_with('-'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn tmux(vars: &[(String, String)], args: &[&str]) -> Option<String> {
+    let output = Command::new("tmux")
+        .args(args)
+        .env_clear()
+        .envs(vars.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Finds the screen or Zellij client attached to our session by scanning
+/// procfs, since neither can be asked about its clients.
+///
+/// Only processes of our own user are considered. The client that started the
+/// session is the server's parent while it stays attached; otherwise a client
+/// must name our session on its command line (or, for Zellij, in its
+/// environment). Returns `None` when no client or more than one matches.
+fn find_client(
+    multiplexer: Multiplexer,
+    vars: &[(String, String)],
+    procfs: &ProcFs,
+    pid: Option<u32>,
+) -> Option<u32> {
+    let pid = pid?;
+    let (uid, _) = procfs.uids(pid)?;
+    let session = Session::from_vars(multiplexer, vars)?;
+    let is_client = |process: &Process| {
+        process
+            .name()
+            .eq_ignore_ascii_case(multiplexer.process_name())
+            && procfs
+                .uids(process.pid)
+                .is_some_and(|(real, _)| real == uid)
+    };
+
+    let ancestors: Vec<Process> = procfs.ancestors(pid).collect();
+    let server = ancestors.iter().find(|process| is_client(process));
+    if let Some(parent) = server
+        .and_then(|server| procfs.process(server.ppid))
+        .filter(|parent| is_client(parent))
+    {
+        return Some(parent.pid);
+    }
+
+    let mut matches = procfs
+        .pids()
+        .filter(|candidate| ancestors.iter().all(|process| process.pid != *candidate))
+        .filter_map(|candidate| procfs.process(candidate))
+        .filter(|process| is_client(process))
+        .filter(|process| session.is_named_by(procfs, process.pid))
+        .map(|process| process.pid);
+
+    match (matches.next(), matches.next()) {
+        (Some(client), None) => Some(client),
+        _ => None,
+    }
+}
+
+/// The session we are running in, as named by the multiplexer's env vars.
+enum Session<'a> {
+    /// screen's `STY`, `<pid>.<name>`, e.g. `4242.pts-0.devbox`.
+    Screen { sty: &'a str },
+    /// Zellij's `ZELLIJ_SESSION_NAME`.
+    Zellij { name: &'a str },
+}
+
+impl<'a> Session<'a> {
+    fn from_vars(multiplexer: Multiplexer, vars: &'a [(String, String)]) -> Option<Self> {
+        let var = |key| var(vars, key).filter(|v| !v.is_empty());
+        match multiplexer {
+            Multiplexer::Screen => var("STY").map(|sty| Self::Screen { sty }),
+            Multiplexer::Zellij => var("ZELLIJ_SESSION_NAME").map(|name| Self::Zellij { name }),
+            Multiplexer::Tmux => None,
+        }
+    }
+
+    /// Whether the client `pid` names this session on its command line, e.g.
+    /// `screen -r devbox` or `zellij attach devbox`, or in its environment.
+    fn is_named_by(&self, procfs: &ProcFs, pid: u32) -> bool {
+        let args = procfs.cmdline(pid).unwrap_or_default();
+        let session_args = args.windows(2).filter_map(|pair| {
+            let (flag, value) = (pair[0].as_str(), pair[1].as_str());
+            let names_session = match self {
+                // Session options may be clustered, as in `-dr` or `-DRS`.
+                Self::Screen { .. } => flag.strip_prefix('-').is_some_and(|cluster| {
+                    !cluster.starts_with('-') && cluster.contains(['r', 'R', 'x', 'd', 'D', 'S'])
+                }),
+                Self::Zellij { .. } => matches!(flag, "attach" | "a" | "-s" | "--session"),
+            };
+            names_session.then_some(value)
+        });
+
+        match *self {
+            Self::Screen { sty } => {
+                let (server_pid, name) = sty.split_once('.').unwrap_or((sty, sty));
+                session_args
+                    .into_iter()
+                    .any(|arg| arg == sty || arg == name || arg == server_pid)
+            }
+            Self::Zellij { name } => {
+                session_args.into_iter().any(|arg| arg == name)
+                    || args
+                        .iter()
+                        .any(|arg| arg.strip_prefix("--session=") == Some(name))
+                    || procfs.environ(pid).is_some_and(|environ| {
+                        environ
+                            .iter()
+                            .any(|(key, value)| key == "ZELLIJ_SESSION_NAME" && value == name)
+                    })
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+/// Options controlling which sources [`detect_with`](crate::detect_with)
+/// consults beyond the environment variables it is given.
+///
+/// # Example
+///
+/// ```
+/// use has_nerd_font::DetectOptions;
+///
+/// let env_vars: Vec<(String, String)> = std::env::vars().collect();
+/// let options = DetectOptions::default().with_proc_root("/proc");
+/// let result = has_nerd_font::detect_with(&env_vars, &options);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectOptions {
+    proc_root: PathBuf,
+    pid: Option<u32>,
+    ancestor_env: bool,
+    terminal_query: bool,
+    glyph_probe: bool,
+    windows_root: PathBuf,
+}
+
+impl Default for DetectOptions {
+    fn default() -> Self {
+        Self {
+            proc_root: PathBuf::from("/proc"),
+            pid: None,
+            ancestor_env: false,
+            terminal_query: false,
+            glyph_probe: false,
+            windows_root: PathBuf::from("/mnt/c"),
+        }
+    }
+}
+
+impl DetectOptions {
+    /// Sets where procfs is mounted. Used on Linux to walk the process tree
+    /// when the terminal cannot be identified from environment variables.
+    ///
+    /// Defaults to `/proc`.
+    pub fn with_proc_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.proc_root = root.into();
+        self
+    }
+
+    /// Runs detection as if from process `pid` instead of the current one.
+    ///
+    /// The environment of `pid` (read from procfs) replaces the `vars` passed
+    /// to [`detect_with`](crate::detect_with), and process-tree lookups start
+    /// from `pid`. If its environment cannot be read, `vars` is used as-is.
+    pub fn with_pid(mut self, pid: u32) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    /// Merges the environments of ancestor processes into `vars` before
+    /// identifying the terminal, so that variables like `TERM_PROGRAM` survive
+    /// `sudo -i` or `doas`. Variables of nearer processes win on conflicts,
+    /// and the process's own environment always wins.
+    ///
+    /// Only ancestors owned by the same user are read; a `sudo` or `doas` hop
+    /// links the invoking user to the target user. Defaults to `false`.
+    pub fn with_ancestor_env(mut self, enabled: bool) -> Self {
+        self.ancestor_env = enabled;
+        self
+    }
+
+    /// Asks the terminal to identify itself by writing XTVERSION and DA2
+    /// queries to `/dev/tty` when it cannot be identified otherwise. Unlike
+    /// config files, this also works over SSH and inside containers.
+    ///
+    /// Terminals that don't reply are skipped after a short timeout. Defaults
+    /// to `false`.
+    pub fn with_terminal_query(mut self, enabled: bool) -> Self {
+        self.terminal_query = enabled;
+        self
+    }
+
+    /// When detection is otherwise inconclusive, prints a few Nerd Font
+    /// glyphs to `/dev/tty` and measures the cursor advance with
+    /// cursor-position reports. The cursor is restored and the glyphs erased
+    /// afterwards. Defaults to `false`.
+    pub fn with_glyph_probe(mut self, enabled: bool) -> Self {
+        self.glyph_probe = enabled;
+        self
+    }
+
+    /// Sets where the Windows system drive is mounted inside WSL. Used to
+    /// read Windows-side settings (Windows Terminal, VS Code) from
+    /// `<root>/Users/<user>`.
+    ///
+    /// Defaults to `/mnt/c`.
+    pub fn with_windows_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.windows_root = root.into();
+        self
+    }
+
+    pub(crate) fn proc_root(&self) -> &Path {
+        &self.proc_root
+    }
+
+    pub(crate) fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    pub(crate) fn ancestor_env(&self) -> bool {
+        self.ancestor_env
+    }
+
+    pub(crate) fn terminal_query(&self) -> bool {
+        self.terminal_query
+    }
+
+    pub(crate) fn glyph_probe(&self) -> bool {
+        self.glyph_probe
+    }
+
+    pub(crate) fn windows_root(&self) -> &Path {
+        &self.windows_root
+    }
+}
@@ -0,0 +1,210 @@
+use crate::tty::{QUERY_TIMEOUT, Reply, Tty, exchange};
+
+/// Nerd Font codepoints from the Private Use Area: a Powerline separator
+/// (U+E0B0), a Font Awesome folder (U+F115) and a file icon (U+F0F6).
+const PROBE_GLYPHS: &[char] = &['\u{e0b0}', '\u{f115}', '\u{f0f6}'];
+/// A codepoint from the end of Plane 16's Private Use Area, which no Nerd Font
+/// covers. It shows how the terminal renders a glyph missing from its fonts,
+/// usually as a one-cell box.
+const REFERENCE_GLYPH: char = '\u{10fffd}';
+
+/// Save cursor (DECSC).
+const SAVE_CURSOR: &[u8] = b"\x1b7";
+/// Restore cursor (DECRC) and erase to the end of the line.
+const RESTORE_CURSOR: &[u8] = b"\x1b8\x1b[K";
+/// Cursor Position Report request, answered as `CSI row ; col R`.
+const CPR: &[u8] = b"\x1b[6n";
+
+/// Prints a reference glyph and then each probe glyph between
+/// cursor-position reports, and compares the cursor advances. A missing glyph
+/// is usually drawn as a box that advances like any other, so only a
+/// difference from the reference tells that the probe glyphs were found.
+///
+/// Returns `Some(false)` if any probe glyph was dropped, `Some(true)` if every
+/// probe glyph advanced the cursor, by a different amount than the reference,
+/// and `None` otherwise: when the advances can't be told apart, the terminal
+/// did not report positions, or the glyphs wrapped onto another line. The
+/// cursor is restored and the probe output erased afterwards.
+pub fn probe(tty: &mut dyn Tty) -> Option<bool> {
+    let glyphs: Vec<char> = std::iter::once(REFERENCE_GLYPH)
+        .chain(PROBE_GLYPHS.iter().copied())
+        .collect();
+
+    let mut request = SAVE_CURSOR.to_vec();
+    request.extend_from_slice(CPR);
+    for glyph in &glyphs {
+        request.extend_from_slice(glyph.encode_utf8(&mut [0; 4]).as_bytes());
+        request.extend_from_slice(CPR);
+    }
+    request.extend_from_slice(RESTORE_CURSOR);
+
+    let replies = exchange(tty, &request, QUERY_TIMEOUT).ok()?;
+    let positions: Vec<(u32, u32)> = replies.iter().filter_map(cursor_position).collect();
+    if positions.len() != glyphs.len() + 1 {
+        return None;
+    }
+
+    let advances = positions
+        .windows(2)
+        .map(|pair| {
+            let ((row_before, col_before), (row_after, col_after)) = (pair[0], pair[1]);
+            (row_before == row_after).then(|| col_after.saturating_sub(col_before))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let (reference, probed) = advances.split_first()?;
+    if probed.contains(&0) {
+        return Some(false);
+    }
+    probed
+        .iter()
+        .all(|advance| advance != reference)
+        .then_some(true)
+}
+
+fn cursor_position(reply: &Reply) -> Option<(u32, u32)> {
+    let Reply::Csi(body) = reply else {
+        return None;
+    };
+    let (row, col) = body.strip_suffix('R')?.split_once(';')?;
+    Some((row.parse().ok()?, col.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io::{self, Read, Write};
+
+    use super::*;
+
+    /// A fake terminal that tracks the cursor column and answers CPR and DA1.
+    struct CprTerminal {
+        row: u32,
+        col: u32,
+        width: u32,
+        glyph_width: u32,
+        /// Width of glyphs missing from every font, like the reference glyph.
+        missing_width: u32,
+        saved: Option<(u32, u32)>,
+        answers_cpr: bool,
+        pending: VecDeque<u8>,
+    }
+
+    impl CprTerminal {
+        fn new(glyph_width: u32) -> Self {
+            Self {
+                row: 5,
+                col: 3,
+                width: 80,
+                glyph_width,
+                missing_width: glyph_width,
+                saved: None,
+                answers_cpr: true,
+                pending: VecDeque::new(),
+            }
+        }
+
+        fn advance(&mut self, cells: u32) {
+            self.col += cells;
+            if self.col > self.width {
+                self.row += 1;
+                self.col -= self.width;
+            }
+        }
+    }
+
+    impl Read for CprTerminal {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.pending.len());
+            for (slot, byte) in buf.iter_mut().zip(self.pending.drain(..n)) {
+                *slot = byte;
+            }
+            Ok(n)
+        }
+    }
+
+    impl Write for CprTerminal {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let mut rest = std::str::from_utf8(buf).expect("probe output should be utf-8");
+            while let Some(c) = rest.chars().next() {
+                if let Some(after) = rest.strip_prefix("\x1b7") {
+                    self.saved = Some((self.row, self.col));
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix("\x1b8") {
+                    (self.row, self.col) = self.saved.expect("restore without save");
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix("\x1b[K") {
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix("\x1b[6n") {
+                    if self.answers_cpr {
+                        let report = format!("\x1b[{};{}R", self.row, self.col);
+                        self.pending.extend(report.bytes());
+                    }
+                    rest = after;
+                } else if let Some(after) = rest.strip_prefix("\x1b[c") {
+                    self.pending.extend(b"\x1b[?62;22c");
+                    rest = after;
+                } else if c == REFERENCE_GLYPH {
+                    self.advance(self.missing_width);
+                    rest = &rest[c.len_utf8()..];
+                } else {
+                    self.advance(self.glyph_width);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn same_width_as_missing_glyph_is_inconclusive() {
+        let mut tty = CprTerminal::new(1);
+        assert_eq!(probe(&mut tty), None);
+    }
+
+    #[test]
+    fn double_width_glyphs_beside_single_width_box() {
+        let mut tty = CprTerminal::new(2);
+        tty.missing_width = 1;
+        assert_eq!(probe(&mut tty), Some(true));
+    }
+
+    #[test]
+    fn glyphs_shown_while_missing_glyph_dropped() {
+        let mut tty = CprTerminal::new(1);
+        tty.missing_width = 0;
+        assert_eq!(probe(&mut tty), Some(true));
+    }
+
+    #[test]
+    fn dropped_glyphs() {
+        let mut tty = CprTerminal::new(0);
+        assert_eq!(probe(&mut tty), Some(false));
+    }
+
+    #[test]
+    fn cursor_is_restored() {
+        let mut tty = CprTerminal::new(1);
+        probe(&mut tty);
+        assert_eq!((tty.row, tty.col), (5, 3));
+    }
+
+    #[test]
+    fn wrapped_line_is_inconclusive() {
+        let mut tty = CprTerminal::new(2);
+        tty.missing_width = 1;
+        tty.col = 79;
+        assert_eq!(probe(&mut tty), None);
+    }
+
+    #[test]
+    fn no_cursor_reports() {
+        let mut tty = CprTerminal::new(1);
+        tty.answers_cpr = false;
+        assert_eq!(probe(&mut tty), None);
+    }
+}
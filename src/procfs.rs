@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+
+/// Upper bound on how many ancestors are visited, in case of a malformed or
+/// cyclic process table.
+const MAX_DEPTH: usize = 64;
+
+/// Read-only view of a procfs mount (normally `/proc`).
+pub struct ProcFs<'a> {
+    root: &'a Path,
+}
+
+/// The subset of a process's procfs entries that detection cares about.
+pub struct Process {
+    pub pid: u32,
+    pub ppid: u32,
+    /// Process name from `/proc/<pid>/comm` (truncated by the kernel to 15 bytes).
+    pub comm: String,
+    /// Resolved target of `/proc/<pid>/exe`, if readable.
+    pub exe: Option<PathBuf>,
+}
+
+impl Process {
+    /// File name of the executable, falling back to `comm` when `exe` is not
+    /// readable (e.g. processes owned by another user).
+    pub fn name(&self) -> &str {
+        self.exe
+            .as_deref()
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.comm)
+    }
+}
+
+impl<'a> ProcFs<'a> {
+    pub fn new(root: &'a Path) -> Self {
+        Self { root }
+    }
+
+    /// PID of the current process, as seen through `<root>/self`.
+    pub fn self_pid(&self) -> Option<u32> {
+        std::fs::read_link(self.root.join("self"))
+            .ok()?
+            .file_name()?
+            .to_str()?
+            .parse()
+            .ok()
+    }
+
+    pub fn process(&self, pid: u32) -> Option<Process> {
+        let dir = self.root.join(pid.to_string());
+        let stat = std::fs::read_to_string(dir.join("stat")).ok()?;
+        let (stat_comm, ppid) = parse_stat(&stat)?;
+
+        let comm = std::fs::read_to_string(dir.join("comm"))
+            .map(|comm| comm.trim_end_matches('\n').to_string())
+            .unwrap_or(stat_comm);
+
+        Some(Process {
+            pid,
+            ppid,
+            comm,
+            exe: std::fs::read_link(dir.join("exe")).ok(),
+        })
+    }
+
+    /// PIDs of all processes visible in procfs.
+    pub fn pids(&self) -> impl Iterator<Item = u32> {
+        std::fs::read_dir(self.root)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+    }
+
+    /// Real and effective UIDs from the `Uid:` line of `/proc/<pid>/status`.
+    pub fn uids(&self, pid: u32) -> Option<(u32, u32)> {
+        let status =
+            std::fs::read_to_string(self.root.join(pid.to_string()).join("status")).ok()?;
+        let line = status.lines().find_map(|line| line.strip_prefix("Uid:"))?;
+        let mut fields = line.split_whitespace().map(str::parse);
+        let real = fields.next()?.ok()?;
+        let effective = fields.next()?.ok()?;
+        Some((real, effective))
+    }
+
+    /// Environment of `pid` from `/proc/<pid>/environ`, in declaration order.
+    pub fn environ(&self, pid: u32) -> Option<Vec<(String, String)>> {
+        let raw = std::fs::read(self.root.join(pid.to_string()).join("environ")).ok()?;
+        Some(
+            raw.split(|&byte| byte == 0)
+                .filter_map(|entry| {
+                    let entry = std::str::from_utf8(entry).ok()?;
+                    let (key, value) = entry.split_once('=')?;
+                    (!key.is_empty()).then(|| (key.to_string(), value.to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Arguments of `pid` from `/proc/<pid>/cmdline`, including the program.
+    pub fn cmdline(&self, pid: u32) -> Option<Vec<String>> {
+        let raw = std::fs::read(self.root.join(pid.to_string()).join("cmdline")).ok()?;
+        Some(
+            raw.split(|&byte| byte == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect(),
+        )
+    }
+
+    /// Working directory of `pid`, from the `cwd` link.
+    pub fn cwd(&self, pid: u32) -> Option<PathBuf> {
+        std::fs::read_link(self.root.join(pid.to_string()).join("cwd")).ok()
+    }
+
+    /// Iterates over the ancestors of `pid`, starting with its parent.
+    pub fn ancestors(&self, pid: u32) -> impl Iterator<Item = Process> + '_ {
+        let mut next = self.process(pid).map(|process| process.ppid);
+        std::iter::from_fn(move || {
+            let ppid = next.take().filter(|&ppid| ppid > 0)?;
+            let process = self.process(ppid)?;
+            next = Some(process.ppid).filter(|&parent| parent != process.pid);
+            Some(process)
+        })
+        .take(MAX_DEPTH)
+    }
+}
+
+impl ProcFs<'_> {
+    /// Iterates over the ancestors of `pid` that belong to the same user,
+    /// stopping at the first one that does not.
+    ///
+    /// A process matches if its real or effective UID was seen on `pid` or a
+    /// matching ancestor, so a setuid hop such as `sudo` (real UID of the
+    /// invoking user, effective UID of root) links both users together.
+    pub fn same_user_ancestors(&self, pid: u32) -> impl Iterator<Item = Process> + '_ {
+        let mut users: Vec<u32> = self
+            .uids(pid)
+            .map(|(real, effective)| vec![real, effective])
+            .unwrap_or_default();
+
+        self.ancestors(pid).map_while(move |process| {
+            let (real, effective) = self.uids(process.pid)?;
+            if !users.contains(&real) && !users.contains(&effective) {
+                return None;
+            }
+            users.extend([real, effective]);
+            Some(process)
+        })
+    }
+}
+
+/// Parses `comm` and `ppid` out of `/proc/<pid>/stat`.
+///
+/// The command name is wrapped in parentheses and may itself contain spaces
+/// or parentheses, so the fields after it are located from the last `)`.
+fn parse_stat(stat: &str) -> Option<(String, u32)> {
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let comm = stat.get(open + 1..close)?.to_string();
+
+    let mut fields = stat.get(close + 1..)?.split_whitespace();
+    let _state = fields.next()?;
+    let ppid = fields.next()?.parse().ok()?;
+
+    Some((comm, ppid))
+}
@@ -0,0 +1,295 @@
+use crate::font::x_font_families;
+use crate::tty::{QUERY_TIMEOUT, Reply, Tty, exchange};
+
+/// XTVERSION: reported as `DCS > | name version ST`.
+const XTVERSION: &[u8] = b"\x1b[>0q";
+/// Secondary Device Attributes: reported as `CSI > Pp ; Pv ; Pc c`.
+const DA2: &[u8] = b"\x1b[>c";
+/// OSC 50 font query: reported as `OSC 50 ; font ST` by xterm and urxvt.
+const OSC50: &[u8] = b"\x1b]50;?\x1b\\";
+
+/// How a terminal identified itself, by a lowercase name such as `kitty` or
+/// `vte`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identity {
+    /// The name in an XTVERSION reply, which names whatever answered.
+    Version(String),
+    /// The name matching a DA2 reply. Many emulators answer DA2 like the
+    /// terminal they emulate, so this is weaker evidence.
+    Attributes(&'static str),
+}
+
+/// Asks the terminal to identify itself. XTVERSION is preferred since it
+/// names the terminal directly; DA2 is only a fallback for terminals that
+/// don't implement it.
+pub fn identify(tty: &mut dyn Tty) -> Option<Identity> {
+    let replies = exchange(tty, &[XTVERSION, DA2].concat(), QUERY_TIMEOUT).ok()?;
+    replies
+        .iter()
+        .find_map(xtversion_name)
+        .map(Identity::Version)
+        .or_else(|| replies.iter().find_map(da2_name).map(Identity::Attributes))
+}
+
+/// Asks the terminal for its active font with OSC 50, returning the families
+/// from the reply in fallback order. Terminals that don't support the query
+/// yield an empty list.
+pub fn font_families(tty: &mut dyn Tty) -> Vec<String> {
+    let Ok(replies) = exchange(tty, OSC50, QUERY_TIMEOUT) else {
+        return Vec::new();
+    };
+    replies
+        .iter()
+        .find_map(|reply| match reply {
+            Reply::Osc(body) => body.strip_prefix("50;"),
+            _ => None,
+        })
+        .filter(|font| !font.is_empty() && *font != "?")
+        .map(x_font_families)
+        .unwrap_or_default()
+}
+
+fn xtversion_name(reply: &Reply) -> Option<String> {
+    let Reply::Dcs(body) = reply else {
+        return None;
+    };
+    version_string_name(body.strip_prefix(">|")?)
+}
+
+/// Extracts the lowercase terminal name from an XTVERSION string, e.g.
+/// `kitty(0.31.0)` or `WezTerm 20240203-110809-5046fc22`.
+pub fn version_string_name(version: &str) -> Option<String> {
+    let name: String = version
+        .trim()
+        .chars()
+        .take_while(|c| !matches!(c, ' ' | '('))
+        .collect();
+    (!name.is_empty()).then(|| name.to_ascii_lowercase())
+}
+
+/// Maps a DA2 reply to a terminal name using the terminal-type and version
+/// parameters that are distinctive enough to trust.
+fn da2_name(reply: &Reply) -> Option<&'static str> {
+    let Reply::Csi(body) = reply else {
+        return None;
+    };
+    let mut params = body
+        .strip_prefix('>')?
+        .strip_suffix('c')?
+        .split(';')
+        .map(|param| param.parse::<u32>().ok());
+    let kind = params.next().flatten()?;
+    let version = params.next().flatten();
+
+    match (kind, version) {
+        (0, Some(95)) => Some("iterm2"),
+        (1, Some(115)) => Some("konsole"),
+        (1, Some(277)) => Some("wezterm"),
+        (1, Some(4000)) => Some("kitty"),
+        (41, _) => Some("xterm"),
+        (65, _) => Some("vte"),
+        (77, _) => Some("mintty"),
+        (83, _) => Some("screen"),
+        (84, _) => Some("tmux"),
+        (85, _) => Some("urxvt"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Terminal;
+    use crate::terminal::TerminalDecision;
+    use crate::tty::Replay;
+
+    const DA1: &[u8] = b"\x1b[c";
+
+    fn identify_with(xtversion: &'static [u8], da2: &'static [u8]) -> Option<String> {
+        let mut tty = Replay::new(&[(XTVERSION, xtversion), (DA2, da2), (DA1, b"\x1b[?62;22c")]);
+        identify(&mut tty).map(|identity| match identity {
+            Identity::Version(name) => name,
+            Identity::Attributes(name) => name.to_string(),
+        })
+    }
+
+    // --- XTVERSION replies ---
+
+    #[test]
+    fn kitty() {
+        let name = identify_with(b"\x1bP>|kitty(0.31.0)\x1b\\", b"\x1b[>1;4000;31c");
+        assert_eq!(name.as_deref(), Some("kitty"));
+    }
+
+    #[test]
+    fn wezterm() {
+        let name = identify_with(
+            b"\x1bP>|WezTerm 20240203-110809-5046fc22\x1b\\",
+            b"\x1b[>1;277;0c",
+        );
+        assert_eq!(name.as_deref(), Some("wezterm"));
+    }
+
+    #[test]
+    fn foot() {
+        let name = identify_with(b"\x1bP>|foot(1.16.2)\x1b\\", b"\x1b[>1;11602;0c");
+        assert_eq!(name.as_deref(), Some("foot"));
+    }
+
+    #[test]
+    fn xterm() {
+        let name = identify_with(b"\x1bP>|XTerm(388)\x1b\\", b"\x1b[>41;388;0c");
+        assert_eq!(name.as_deref(), Some("xterm"));
+    }
+
+    #[test]
+    fn ghostty() {
+        let name = identify_with(b"\x1bP>|ghostty 1.0.1\x1b\\", b"\x1b[>1;10;0c");
+        assert_eq!(name.as_deref(), Some("ghostty"));
+    }
+
+    #[test]
+    fn iterm2() {
+        let name = identify_with(b"\x1bP>|iTerm2 3.5.4\x1b\\", b"\x1b[>0;95;0c");
+        assert_eq!(name.as_deref(), Some("iterm2"));
+    }
+
+    #[test]
+    fn contour() {
+        let name = identify_with(b"\x1bP>|contour 0.4.3\x1b\\", b"\x1b[>1;0;0c");
+        assert_eq!(name.as_deref(), Some("contour"));
+    }
+
+    #[test]
+    fn rio() {
+        let name = identify_with(b"\x1bP>|Rio 0.2.12\x1b\\", b"\x1b[>0;0;0c");
+        assert_eq!(name.as_deref(), Some("rio"));
+    }
+
+    #[test]
+    fn tmux() {
+        let name = identify_with(b"\x1bP>|tmux 3.4\x1b\\", b"\x1b[>84;0;0c");
+        assert_eq!(name.as_deref(), Some("tmux"));
+    }
+
+    #[test]
+    fn xtversion_terminated_by_bel() {
+        let name = identify_with(b"\x1bP>|kitty(0.31.0)\x07", b"");
+        assert_eq!(name.as_deref(), Some("kitty"));
+    }
+
+    // --- Mapping replies to terminals ---
+
+    fn terminal_with(xtversion: &'static [u8], da2: &'static [u8]) -> Option<Terminal> {
+        let mut tty = Replay::new(&[(XTVERSION, xtversion), (DA2, da2), (DA1, b"")]);
+        match crate::terminal::detect_from_query(&mut tty) {
+            TerminalDecision::Identified(terminal) | TerminalDecision::Bundled(terminal) => {
+                Some(terminal)
+            }
+            TerminalDecision::Unknown => None,
+        }
+    }
+
+    #[test]
+    fn contour_reply_identifies_contour() {
+        let terminal = terminal_with(b"\x1bP>|contour 0.4.3\x1b\\", b"");
+        assert_eq!(terminal, Some(Terminal::Contour));
+    }
+
+    #[test]
+    fn rio_reply_identifies_rio() {
+        let terminal = terminal_with(b"\x1bP>|Rio 0.2.12\x1b\\", b"");
+        assert_eq!(terminal, Some(Terminal::Rio));
+    }
+
+    #[test]
+    fn xterm_reply_identifies_xterm() {
+        let terminal = terminal_with(b"\x1bP>|XTerm(388)\x1b\\", b"\x1b[>41;388;0c");
+        assert_eq!(terminal, Some(Terminal::Xterm));
+    }
+
+    #[test]
+    fn xterm_da2_alone_is_inconclusive() {
+        let terminal = terminal_with(b"", b"\x1b[>41;388;0c");
+        assert_eq!(terminal, None);
+    }
+
+    #[test]
+    fn tmux_reply_is_inconclusive() {
+        let terminal = terminal_with(b"\x1bP>|tmux 3.4\x1b\\", b"\x1b[>84;0;0c");
+        assert_eq!(terminal, None);
+    }
+
+    #[test]
+    fn screen_da2_is_inconclusive() {
+        let terminal = terminal_with(b"", b"\x1b[>83;40903;0c");
+        assert_eq!(terminal, None);
+    }
+
+    // --- DA2 fallback for terminals without XTVERSION ---
+
+    #[test]
+    fn konsole_da2() {
+        let name = identify_with(b"", b"\x1b[>1;115;0c");
+        assert_eq!(name.as_deref(), Some("konsole"));
+    }
+
+    #[test]
+    fn vte_da2() {
+        let name = identify_with(b"", b"\x1b[>65;7600;1c");
+        assert_eq!(name.as_deref(), Some("vte"));
+    }
+
+    #[test]
+    fn unrecognized_da2() {
+        let name = identify_with(b"", b"\x1b[>1;10;0c");
+        assert_eq!(name, None);
+    }
+
+    // --- Degrading silently ---
+
+    #[test]
+    fn silent_terminal() {
+        let mut tty = Replay::new(&[]);
+        assert!(identify(&mut tty).is_none());
+        assert_eq!(tty.written, b"\x1b[>0q\x1b[>c\x1b[c");
+    }
+
+    #[test]
+    fn stray_input_is_ignored() {
+        let name = identify_with(b"ls\r\x1bP>|foot(1.16.2)\x1b\\", b"\x1b[>1;11602");
+        assert_eq!(name.as_deref(), Some("foot"));
+    }
+
+    // --- OSC 50 font queries ---
+
+    fn font_with(reply: &'static [u8]) -> Vec<String> {
+        let mut tty = Replay::new(&[(OSC50, reply), (DA1, b"\x1b[?62;22c")]);
+        font_families(&mut tty)
+    }
+
+    #[test]
+    fn xterm_xlfd_font() {
+        let families = font_with(
+            b"\x1b]50;-misc-fixed-medium-r-semicondensed--13-120-75-75-c-60-iso10646-1\x1b\\",
+        );
+        assert_eq!(families, ["fixed"]);
+    }
+
+    #[test]
+    fn xterm_xft_font() {
+        let families = font_with(b"\x1b]50;xft:Hack Nerd Font Mono:size=11\x07");
+        assert_eq!(families, ["Hack Nerd Font Mono"]);
+    }
+
+    #[test]
+    fn urxvt_font_list() {
+        let families = font_with(b"\x1b]50;xft:Iosevka:size=12,xft:Symbols Nerd Font:size=12\x07");
+        assert_eq!(families, ["Iosevka", "Symbols Nerd Font"]);
+    }
+
+    #[test]
+    fn font_query_unsupported() {
+        assert!(font_with(b"").is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::var;
+
+/// How long a remembered answer stays valid. Clients get new terminals and
+/// fonts, so stale entries fall back to the usual remote-session result.
+const HINT_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Remembered answers, keyed by SSH client address.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store(BTreeMap<String, Hint>);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Hint {
+    detected: bool,
+    /// Seconds since the Unix epoch.
+    saved_at: u64,
+}
+
+impl Hint {
+    fn is_fresh(&self, now: u64) -> bool {
+        now.saturating_sub(self.saved_at) < HINT_TTL.as_secs()
+    }
+}
+
+/// A remembered answer for the current SSH client.
+pub struct StoredAnswer {
+    pub detected: bool,
+    pub path: PathBuf,
+}
+
+/// Looks up a fresh answer remembered for the connecting SSH client.
+pub fn lookup(vars: &[(String, String)]) -> Option<StoredAnswer> {
+    let address = client_address(vars)?;
+    let path = store_path(vars)?;
+    let store = read_store(&path).ok()?;
+    let hint = store.0.get(address).filter(|hint| hint.is_fresh(now()))?;
+    Some(StoredAnswer {
+        detected: hint.detected,
+        path,
+    })
+}
+
+/// Remembers `detected` for the connecting SSH client, dropping expired
+/// entries along the way. Returns the path of the store.
+pub fn remember(vars: &[(String, String)], detected: bool) -> io::Result<PathBuf> {
+    let address = client_address(vars)
+        .ok_or_else(|| io::Error::other("no SSH client address in SSH_CONNECTION or SSH_CLIENT"))?;
+    let path = store_path(vars)
+        .ok_or_else(|| io::Error::other("neither XDG_STATE_HOME nor HOME is set"))?;
+
+    let now = now();
+    // Writing over a store that can't be read would lose every other
+    // client's answer.
+    let mut store = match read_store(&path) {
+        Ok(store) => store,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Store::default(),
+        Err(e) => {
+            return Err(io::Error::new(
+                e.kind(),
+                format!("cannot read {}: {e}", path.display()),
+            ));
+        }
+    };
+    store.0.retain(|_, hint| hint.is_fresh(now));
+    store.0.insert(
+        address.to_string(),
+        Hint {
+            detected,
+            saved_at: now,
+        },
+    );
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(&store).map_err(io::Error::other)?;
+    write_atomically(&path, &(json + "\n"))?;
+    Ok(path)
+}
+
+/// The client address is the first field of `SSH_CONNECTION`
+/// (`client_ip client_port server_ip server_port`) or of the older
+/// `SSH_CLIENT` (`client_ip client_port server_port`).
+fn client_address(vars: &[(String, String)]) -> Option<&str> {
+    ["SSH_CONNECTION", "SSH_CLIENT"]
+        .into_iter()
+        .find_map(|key| var(vars, key)?.split_whitespace().next())
+}
+
+fn store_path(vars: &[(String, String)]) -> Option<PathBuf> {
+    let state_home = match var(vars, "XDG_STATE_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(var(vars, "HOME")?).join(".local/state"),
+    };
+    Some(state_home.join("has-nerd-font/remote-hints.json"))
+}
+
+fn read_store(path: &Path) -> io::Result<Store> {
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(io::Error::other)
+}
+
+/// Writes `content` to a temporary file next to `path` and renames it into
+/// place, so a crash mid-write leaves the previous store intact.
+fn write_atomically(path: &Path, content: &str) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(format!(".{}.tmp", std::process::id()));
+    let temp = PathBuf::from(temp);
+
+    let written = std::fs::write(&temp, content).and_then(|()| std::fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    written
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
@@ -1,4 +1,8 @@
 use crate::Terminal;
+use crate::multiplexer::Multiplexer;
+use crate::procfs::ProcFs;
+use crate::query::Identity;
+use crate::tty::Tty;
 
 pub enum TerminalDecision {
     Bundled(Terminal),
@@ -23,6 +27,13 @@ pub fn detect(vars: &[(String, String)]) -> TerminalDecision {
         return decide(terminal);
     }
 
+    // Check LC_TERMINAL, which iTerm2 sets and sshd forwards like other LC_* vars
+    if let Some(value) = var("LC_TERMINAL")
+        && let Some(terminal) = from_lc_terminal(value)
+    {
+        return decide(terminal);
+    }
+
     // Check terminal-specific env vars
     if var("OPENCODE_TERMINAL") == Some("1") {
         return decide(Terminal::OpenCode);
@@ -40,17 +51,115 @@ pub fn detect(vars: &[(String, String)]) -> TerminalDecision {
         return decide(Terminal::Alacritty);
     }
 
-    // Fall back to Unknown if TERM_PROGRAM was set but unrecognized
-    if let Some(value) = var("TERM_PROGRAM") {
-        let raw = value.trim();
-        if !raw.is_empty() {
-            return TerminalDecision::Identified(Terminal::Unknown(raw.to_string()));
+    if var("TERMINAL_NAME") == Some("contour") {
+        return decide(Terminal::Contour);
+    }
+
+    if var("TERMUX_VERSION").is_some_and(|v| !v.is_empty())
+        || var("PREFIX").is_some_and(|v| v.starts_with("/data/data/com.termux/"))
+    {
+        return decide(Terminal::Termux);
+    }
+
+    if var("TERMINAL_EMULATOR") == Some("JetBrains-JediTerm") {
+        return decide(Terminal::JetBrains);
+    }
+
+    if var("WT_SESSION").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::WindowsTerminal);
+    }
+
+    // Yakuake embeds Konsole, so both set KONSOLE_VERSION.
+    if var("KONSOLE_DBUS_SERVICE").is_some_and(|v| v.starts_with("org.kde.yakuake")) {
+        return decide(Terminal::Yakuake);
+    }
+
+    if var("KONSOLE_VERSION").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::Konsole);
+    }
+
+    if var("PTYXIS_VERSION").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::Ptyxis);
+    }
+
+    if ["GNOME_TERMINAL_SCREEN", "GNOME_TERMINAL_SERVICE"]
+        .into_iter()
+        .any(|key| var(key).is_some_and(|v| !v.is_empty()))
+    {
+        return decide(Terminal::GnomeTerminal);
+    }
+
+    if var("TERMINATOR_UUID").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::Terminator);
+    }
+
+    if var("TILIX_ID").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::Tilix);
+    }
+
+    if var("XTERM_VERSION").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::Xterm);
+    }
+
+    if var("COLORTERM").is_some_and(|v| v.trim() == "rxvt-xpm") {
+        return decide(Terminal::Urxvt);
+    }
+
+    // xfce4-terminal set COLORTERM to its own name before 0.8.
+    if var("COLORTERM").is_some_and(|v| v.trim().eq_ignore_ascii_case("xfce4-terminal")) {
+        return decide(Terminal::Xfce4Terminal);
+    }
+
+    // Every VTE-based terminal sets VTE_VERSION, so this goes after the
+    // terminal-specific checks.
+    if var("VTE_VERSION").is_some_and(|v| !v.is_empty()) {
+        return decide(Terminal::Vte);
+    }
+
+    // Fall back to Unknown if TERM_PROGRAM or LC_TERMINAL was set but unrecognized
+    for key in ["TERM_PROGRAM", "LC_TERMINAL"] {
+        if let Some(value) = var(key) {
+            let raw = value.trim();
+            if !raw.is_empty() {
+                return TerminalDecision::Identified(Terminal::Unknown(raw.to_string()));
+            }
         }
     }
 
     TerminalDecision::Unknown
 }
 
+/// Identifies the terminal by walking up the process tree until a known
+/// emulator binary is found.
+pub fn detect_from_process_tree(procfs: &ProcFs, pid: u32) -> TerminalDecision {
+    procfs
+        .ancestors(pid)
+        .find_map(|process| from_process_name(process.name()))
+        .map_or(TerminalDecision::Unknown, decide)
+}
+
+/// Identifies the terminal by asking it directly with XTVERSION and DA2.
+pub fn detect_from_query(tty: &mut dyn Tty) -> TerminalDecision {
+    match crate::query::identify(tty) {
+        Some(Identity::Version(name)) => detect_from_name(name),
+        // Many emulators answer DA2 like xterm, so "xterm" there proves
+        // nothing about which config applies.
+        Some(Identity::Attributes("xterm")) | None => TerminalDecision::Unknown,
+        Some(Identity::Attributes(name)) => detect_from_name(name.to_string()),
+    }
+}
+
+/// Identifies the terminal from a name reported by the terminal itself, e.g.
+/// via XTVERSION or a multiplexer's record of its client.
+pub fn detect_from_name(name: String) -> TerminalDecision {
+    // A multiplexer answers queries itself unless it passes them through, so
+    // its reply says nothing about the terminal around it.
+    if Multiplexer::from_name(&name).is_some() {
+        return TerminalDecision::Unknown;
+    }
+    decide(from_query_name(name))
+}
+
 fn decide(terminal: Terminal) -> TerminalDecision {
     if terminal.is_bundled() {
         TerminalDecision::Bundled(terminal)
@@ -71,15 +180,77 @@ fn from_term_program(value: &str) -> Option<Terminal> {
         "vscode" => Some(Terminal::Vscode),
         "zed" => Some(Terminal::Zed),
         "hyper" => Some(Terminal::Hyper),
+        "xfce4-terminal" => Some(Terminal::Xfce4Terminal),
+        "warpterminal" => Some(Terminal::Warp),
+        "tabby" => Some(Terminal::Tabby),
+        "waveterm" => Some(Terminal::Wave),
+        "rio" => Some(Terminal::Rio),
         _ => None,
     }
 }
 
+fn from_lc_terminal(value: &str) -> Option<Terminal> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "iterm2" => Some(Terminal::ITerm2),
+        _ => from_term_program(value),
+    }
+}
+
 fn from_term(value: &str) -> Option<Terminal> {
     let normalized = value.trim().to_ascii_lowercase();
     match normalized.as_str() {
         "xterm-ghostty" => Some(Terminal::Ghostty),
         "xterm-kitty" => Some(Terminal::Kitty),
+        "alacritty" => Some(Terminal::Alacritty),
+        "foot" | "foot-direct" | "foot-extra" | "foot-extra-direct" => Some(Terminal::Foot),
+        "rio" => Some(Terminal::Rio),
+        "contour" | "contour-latest" => Some(Terminal::Contour),
+        term if term.starts_with("rxvt-unicode") => Some(Terminal::Urxvt),
         _ => None,
     }
 }
+
+fn from_process_name(value: &str) -> Option<Terminal> {
+    match value {
+        "alacritty" => Some(Terminal::Alacritty),
+        "kitty" => Some(Terminal::Kitty),
+        "ghostty" => Some(Terminal::Ghostty),
+        "wezterm-gui" => Some(Terminal::WezTerm),
+        "zed" | "zed-editor" => Some(Terminal::Zed),
+        "code" | "code-oss" | "codium" => Some(Terminal::Vscode),
+        "hyper" => Some(Terminal::Hyper),
+        "warp-terminal" | "warp-terminal-preview" => Some(Terminal::Warp),
+        "tabby" => Some(Terminal::Tabby),
+        "waveterm" => Some(Terminal::Wave),
+        "rio" => Some(Terminal::Rio),
+        "contour" => Some(Terminal::Contour),
+        // gnome-terminal-server's comm is truncated by the kernel.
+        "gnome-terminal-server" | "gnome-terminal-" => Some(Terminal::GnomeTerminal),
+        "ptyxis" | "ptyxis-agent" => Some(Terminal::Ptyxis),
+        "konsole" => Some(Terminal::Konsole),
+        "yakuake" => Some(Terminal::Yakuake),
+        "foot" | "footclient" => Some(Terminal::Foot),
+        "xfce4-terminal" => Some(Terminal::Xfce4Terminal),
+        "tilix" => Some(Terminal::Tilix),
+        "xterm" => Some(Terminal::Xterm),
+        "urxvt" | "urxvtd" => Some(Terminal::Urxvt),
+        _ => None,
+    }
+}
+
+fn from_query_name(name: String) -> Terminal {
+    match name.as_str() {
+        "kitty" => Terminal::Kitty,
+        "wezterm" => Terminal::WezTerm,
+        "ghostty" => Terminal::Ghostty,
+        "iterm2" => Terminal::ITerm2,
+        "vte" => Terminal::Vte,
+        "konsole" => Terminal::Konsole,
+        "foot" => Terminal::Foot,
+        "urxvt" => Terminal::Urxvt,
+        "xterm" => Terminal::Xterm,
+        "contour" => Terminal::Contour,
+        "rio" => Terminal::Rio,
+        _ => Terminal::Unknown(name),
+    }
+}
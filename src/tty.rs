@@ -0,0 +1,223 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long to wait for a terminal to answer a batch of queries. Every batch
+/// ends with a DA1 request, which all terminals answer, so this only elapses
+/// in full when the terminal is not answering at all.
+pub const QUERY_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Primary Device Attributes request, appended to every batch as a sentinel.
+const DA1: &[u8] = b"\x1b[c";
+
+/// A terminal that can be written to and read from.
+///
+/// Reads must not block indefinitely: returning `Ok(0)` means no data arrived
+/// within a short poll interval.
+pub trait Tty: Read + Write {}
+
+impl<T: Read + Write> Tty for T {}
+
+/// The controlling terminal, switched to raw mode with a 100ms read timeout
+/// for as long as it is open.
+pub struct DevTty {
+    file: File,
+    saved: String,
+}
+
+impl DevTty {
+    pub fn open() -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+        let saved = stty(&file, &["-g"])?;
+        stty(&file, &["raw", "-echo", "min", "0", "time", "1"])?;
+        Ok(Self { file, saved })
+    }
+}
+
+impl Read for DevTty {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl Write for DevTty {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for DevTty {
+    fn drop(&mut self) {
+        let _ = stty(&self.file, &[self.saved.trim()]);
+    }
+}
+
+fn stty(tty: &File, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(tty.try_clone()?)
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A control sequence sent back by the terminal, without its introducer and
+/// terminator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// `CSI ... F`, including the final byte (e.g. `>1;4000;29c`).
+    Csi(String),
+    /// `DCS ... ST` (e.g. `>|kitty(0.31.0)`).
+    Dcs(String),
+    /// `OSC ... ST` (e.g. `50;xft:Hack:size=11`).
+    Osc(String),
+}
+
+impl Reply {
+    fn is_primary_attributes(&self) -> bool {
+        matches!(self, Self::Csi(body) if body.starts_with('?') && body.ends_with('c'))
+    }
+}
+
+/// Writes `queries` followed by a DA1 request and collects the replies until
+/// the DA1 answer arrives or `timeout` passes.
+pub fn exchange(tty: &mut dyn Tty, queries: &[u8], timeout: Duration) -> io::Result<Vec<Reply>> {
+    let mut request = queries.to_vec();
+    request.extend_from_slice(DA1);
+    tty.write_all(&request)?;
+    tty.flush()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut received = Vec::new();
+    let mut chunk = [0; 256];
+    loop {
+        let replies = parse_replies(&received);
+        if replies.iter().any(Reply::is_primary_attributes) || Instant::now() >= deadline {
+            return Ok(replies);
+        }
+
+        match tty.read(&mut chunk) {
+            Ok(n) => received.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Splits raw terminal output into complete CSI, DCS and OSC sequences.
+/// Anything else (including a trailing incomplete sequence) is skipped.
+pub fn parse_replies(bytes: &[u8]) -> Vec<Reply> {
+    let mut replies = Vec::new();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        if bytes[i] != 0x1b {
+            i += 1;
+            continue;
+        }
+
+        let start = i + 2;
+        let parsed = match bytes[i + 1] {
+            b'[' => control_sequence_len(&bytes[start..])
+                .map(|len| (Reply::Csi(lossy(&bytes[start..start + len])), start + len)),
+            b'P' => string_terminated(&bytes[start..])
+                .map(|(body, len)| (Reply::Dcs(lossy(body)), start + len)),
+            b']' => string_terminated(&bytes[start..])
+                .map(|(body, len)| (Reply::Osc(lossy(body)), start + len)),
+            _ => None,
+        };
+
+        match parsed {
+            Some((reply, next)) => {
+                replies.push(reply);
+                i = next;
+            }
+            None => i += 1,
+        }
+    }
+    replies
+}
+
+/// Length of a CSI body: parameter and intermediate bytes followed by a
+/// final byte.
+fn control_sequence_len(bytes: &[u8]) -> Option<usize> {
+    let end = bytes.iter().position(|b| !(0x20..=0x3f).contains(b))?;
+    (0x40..=0x7e).contains(&bytes[end]).then_some(end + 1)
+}
+
+/// Finds the end of a string terminated by ST (`ESC \`) or BEL. Returns the
+/// body and the number of bytes consumed including the terminator.
+fn string_terminated(bytes: &[u8]) -> Option<(&[u8], usize)> {
+    let end = bytes.iter().position(|&b| b == 0x07 || b == 0x1b)?;
+    match bytes[end] {
+        0x07 => Some((&bytes[..end], end + 1)),
+        _ => (bytes.get(end + 1) == Some(&b'\\')).then_some((&bytes[..end], end + 2)),
+    }
+}
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// A stand-in terminal that answers known queries with recorded replies.
+#[cfg(test)]
+pub struct Replay {
+    replies: Vec<(&'static [u8], &'static [u8])>,
+    pending: std::collections::VecDeque<u8>,
+    pub written: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Replay {
+    pub fn new(replies: &[(&'static [u8], &'static [u8])]) -> Self {
+        Self {
+            replies: replies.to_vec(),
+            pending: Default::default(),
+            written: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Read for Replay {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.pending.len());
+        for (slot, byte) in buf.iter_mut().zip(self.pending.drain(..n)) {
+            *slot = byte;
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+impl Write for Replay {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written.extend_from_slice(buf);
+        let mut i = 0;
+        while i < buf.len() {
+            match self
+                .replies
+                .iter()
+                .find(|(query, _)| buf[i..].starts_with(query))
+            {
+                Some((query, reply)) => {
+                    self.pending.extend(reply.iter());
+                    i += query.len();
+                }
+                None => i += 1,
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -32,6 +32,13 @@ pub struct DetectionResult {
     /// The terminal profile that was inspected, if applicable (e.g. iTerm2 profiles).
     pub profile: Option<String>,
 
+    /// The build or fork of the terminal whose settings were read, for
+    /// terminals that come in several (e.g. `Cursor` for VS Code). For a
+    /// [`RemoteSession`](DetectionSource::RemoteSession), the kind of remote
+    /// when known (e.g. `dev-container`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+
     /// A human-readable error message when detection failed due to a config error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_reason: Option<String>,
@@ -51,7 +58,8 @@ pub enum DetectionSource {
     ExplicitDisable,
     /// The terminal emulator could not be identified.
     UnknownTerminal,
-    /// An SSH session was detected; local config files are not accessible.
+    /// An SSH session or an editor's remote window was detected; local config
+    /// files are not accessible.
     RemoteSession,
     /// The terminal was identified but has no config parser implemented.
     NoResolver,
@@ -61,6 +69,16 @@ pub enum DetectionSource {
     BundledTerminal,
     /// The font was read from the terminal's configuration file.
     TerminalConfig,
+    /// Nerd Font glyphs were printed to the terminal and the cursor advance
+    /// was measured. Reported with [`Confidence::Probable`].
+    GlyphProbe,
+    /// The terminal reported its font in reply to an OSC 50 query.
+    FontQuery,
+    /// In an SSH session, the client forwarded its answer in `LC_NERD_FONT`.
+    ForwardedEnv,
+    /// In an SSH session, an answer remembered for the connecting client (see
+    /// [`remember`](crate::remember)) was used.
+    StoredAnswer,
 }
 
 /// A recognized terminal emulator.
@@ -90,8 +108,56 @@ pub enum Terminal {
     Vscode,
     /// [Zed](https://zed.dev/) — detected via settings.
     Zed,
-    /// [Hyper](https://hyper.is/) — identified but no config parser implemented.
+    /// [Windows Terminal](https://github.com/microsoft/terminal) — detected via
+    /// settings, read from inside WSL.
+    WindowsTerminal,
+    /// [GNOME Terminal](https://gitlab.gnome.org/GNOME/gnome-terminal) —
+    /// detected via dconf.
+    GnomeTerminal,
+    /// [Ptyxis](https://gitlab.gnome.org/chergert/ptyxis) — detected via dconf.
+    Ptyxis,
+    /// [Konsole](https://konsole.kde.org/) — detected via profile files.
+    Konsole,
+    /// [Yakuake](https://apps.kde.org/yakuake/) — detected via Konsole profile
+    /// files.
+    Yakuake,
+    /// [foot](https://codeberg.org/dnkl/foot) — detected via config file.
+    Foot,
+    /// [Terminator](https://gnome-terminator.org/) — detected via config file.
+    Terminator,
+    /// [Tilix](https://gnunn1.github.io/tilix-web/) — detected via dconf
+    /// settings.
+    Tilix,
+    /// [xfce4-terminal](https://docs.xfce.org/apps/xfce4-terminal/start) —
+    /// detected via xfconf settings.
+    Xfce4Terminal,
+    /// [xterm](https://invisible-island.net/xterm/) — detected via X
+    /// resources.
+    Xterm,
+    /// [rxvt-unicode](http://software.schmorp.de/pkg/rxvt-unicode.html) —
+    /// detected via X resources.
+    Urxvt,
+    /// The terminal of a [JetBrains](https://www.jetbrains.com/) IDE or Android
+    /// Studio — detected via IDE settings.
+    JetBrains,
+    /// Another terminal built on [VTE](https://gitlab.gnome.org/GNOME/vte) —
+    /// identified but no config parser implemented.
+    Vte,
+    /// [Hyper](https://hyper.is/) — detected via config file.
     Hyper,
+    /// [Warp](https://www.warp.dev/) — detected via preferences.
+    Warp,
+    /// [Tabby](https://tabby.sh/) — detected via config file.
+    Tabby,
+    /// [Wave](https://www.waveterm.dev/) — detected via settings.
+    Wave,
+    /// [Rio](https://rioterm.com/) — detected via config file.
+    Rio,
+    /// [Contour](https://contour-terminal.org/) — detected via config file.
+    Contour,
+    /// [Termux](https://termux.dev/) (Android) — detected via the custom font
+    /// file.
+    Termux,
     /// A terminal that was not recognized. Contains the raw identifier string.
     Unknown(String),
 }
@@ -0,0 +1,140 @@
+mod support;
+
+use has_nerd_font::{DetectOptions, detect_with};
+use insta::assert_snapshot;
+
+fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// user shell (1000) -> sudo (setuid root) -> root shell (0) -> has-nerd-font (0)
+fn sudo_tree(name: &str) -> (std::path::PathBuf, support::FakeProc) {
+    let home = support::scenario_home(name);
+    let proc = support::FakeProc::new(&home, 400);
+    proc.add(100, 1, "zsh", Some("/usr/bin/zsh"));
+    proc.add(200, 100, "sudo", Some("/usr/bin/sudo"));
+    proc.set_uids(200, 1000, 0);
+    proc.add(300, 200, "bash", Some("/usr/bin/bash"));
+    proc.set_uids(300, 0, 0);
+    proc.add(400, 300, "has-nerd-font", None);
+    proc.set_uids(400, 0, 0);
+    (home, proc)
+}
+
+#[test]
+fn ancestor_env_recovers_term_program_across_sudo() {
+    let (_home, proc) = sudo_tree("ancestor-env-sudo");
+    proc.set_environ(100, &[("TERM_PROGRAM", "ghostty"), ("HOME", "/home/me")]);
+    proc.set_environ(300, &[("HOME", "/root")]);
+
+    let options = DetectOptions::default()
+        .with_proc_root(proc.root())
+        .with_ancestor_env(true);
+    let result = detect_with(&vars(&[("HOME", "/root")]), &options);
+
+    assert_snapshot!(
+        "ancestor_env_sudo_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn ancestor_env_is_opt_in() {
+    let (_home, proc) = sudo_tree("ancestor-env-opt-in");
+    proc.set_environ(100, &[("TERM_PROGRAM", "CoolNewTerm")]);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(&[], &options);
+
+    assert_snapshot!(
+        "ancestor_env_opt_in_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn ancestor_env_own_vars_and_nearer_ancestors_win() {
+    let (_home, proc) = sudo_tree("ancestor-env-precedence");
+    proc.set_environ(100, &[("TERM_PROGRAM", "Hyper"), ("NERD_FONT", "1")]);
+    proc.set_environ(300, &[("TERM_PROGRAM", "CoolNewTerm")]);
+
+    let options = DetectOptions::default()
+        .with_proc_root(proc.root())
+        .with_ancestor_env(true);
+    let result = detect_with(&vars(&[("NERD_FONT", "maybe")]), &options);
+
+    assert_snapshot!(
+        "ancestor_env_precedence_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn ancestor_env_skips_other_users() {
+    let home = support::scenario_home("ancestor-env-other-user");
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(100, 1, "login", None);
+    proc.set_uids(100, 1001, 1001);
+    proc.set_environ(100, &[("TERM_PROGRAM", "ghostty")]);
+    proc.add(200, 100, "bash", None);
+    proc.add(300, 200, "has-nerd-font", None);
+
+    let options = DetectOptions::default()
+        .with_proc_root(proc.root())
+        .with_ancestor_env(true);
+    let result = detect_with(&[], &options);
+
+    assert_snapshot!(
+        "ancestor_env_other_user_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn pid_runs_cascade_as_another_process() {
+    let home = support::scenario_home("ancestor-env-pid");
+    let proc = support::FakeProc::new(&home, 900);
+    proc.add(100, 1, "kitty", Some("/usr/bin/kitty"));
+    proc.add(200, 100, "fish", None);
+    proc.set_environ(200, &[("SHELL", "/usr/bin/fish")]);
+    proc.add(900, 1, "has-nerd-font", None);
+
+    let options = DetectOptions::default()
+        .with_proc_root(proc.root())
+        .with_pid(200);
+    let result = detect_with(&vars(&[("TERM_PROGRAM", "ghostty")]), &options);
+
+    assert_snapshot!(
+        "ancestor_env_pid_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn pid_flag_reads_real_process_environment() {
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .env_clear()
+        .env("TERM_PROGRAM", "kitty")
+        .spawn()
+        .expect("failed to spawn sleep");
+    let pid = child.id().to_string();
+
+    let output = support::run_cli(
+        &["--json", "--explain", "--pid", &pid],
+        &[("TERM_PROGRAM", "CoolNewTerm")],
+        None,
+    );
+    child.kill().expect("failed to kill sleep");
+    child.wait().expect("failed to reap sleep");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "ancestor_env_pid_flag_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
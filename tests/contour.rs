@@ -0,0 +1,91 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+const CONFIG: &str = ".config/contour/contour.yml";
+
+fn run_contour(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    support::run_cli(
+        &["--json", "--explain"],
+        &[("HOME", &home_str), ("TERMINAL_NAME", "contour")],
+        None,
+    )
+}
+
+#[test]
+fn contour_default_profile() {
+    let home = support::scenario_home("contour-default-profile");
+    support::install_config_fixture(&home, "contour", "contour-default-profile.yml", CONFIG);
+
+    let output = run_contour(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "contour_default_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "contour_default_profile_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn contour_main_profile_with_family_string() {
+    let home = support::scenario_home("contour-main");
+    support::install_config_fixture(&home, "contour", "contour-main.yml", CONFIG);
+
+    let output = run_contour(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("contour_main_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn contour_without_font_uses_default() {
+    let home = support::scenario_home("contour-no-font");
+    support::install_config_fixture(&home, "contour", "contour-no-font.yml", CONFIG);
+
+    let output = run_contour(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "contour_no_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn contour_without_config_uses_default() {
+    let home = support::scenario_home("contour-no-config");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[("HOME", &home_str), ("TERM", "contour")],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "contour_no_config_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn contour_malformed_config() {
+    let home = support::scenario_home("contour-malformed");
+    support::install_config_fixture(&home, "contour", "contour-malformed.yml", CONFIG);
+
+    let output = run_contour(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "contour_malformed_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,8 @@
+"use strict";
+module.exports.config = {
+    fontSize: 13,
+    fontFamily: '"JetBrainsMono Nerd Font", Menlo, monospace',
+    cursorShape: 'BLOCK',
+};
+module.exports.plugins = [];
+module.exports.localPlugins = [];
@@ -0,0 +1,35 @@
+"use strict";
+// Future versions of Hyper may add additional config options,
+// which will not automatically be merged into this file.
+// See https://hyper.is#cfg for all currently supported options.
+module.exports = {
+    config: {
+        // choose either `'stable'` for receiving highly polished,
+        // or `'canary'` for less polished but more frequent updates
+        updateChannel: 'stable',
+        // default font size in pixels for all tabs
+        fontSize: 12,
+        // font family with optional fallbacks
+        // fontFamily: 'Fira Code',
+        fontFamily: 'Menlo, "DejaVu Sans Mono", "Hack Nerd Font Mono", monospace',
+        // custom CSS to embed in the main window
+        css: '.tab_text { color: #fff; }',
+        termCSS: `
+          x-screen a { color: red; }
+        `,
+        padding: '12px 14px',
+        colors: {
+            black: '#000000',
+            red: '#C51E14',
+        },
+        env: {},
+        bell: 'SOUND',
+    },
+    plugins: ["hyper-snazzy"],
+    localPlugins: [],
+    keymaps: {
+    // Example
+    // 'window:devtools': 'cmd+alt+o',
+    },
+};
+//# sourceMappingURL=config-default.js.map
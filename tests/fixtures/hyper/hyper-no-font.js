@@ -0,0 +1,7 @@
+module.exports = {
+  config: {
+    fontSize: 14,
+    cursorShape: 'BEAM',
+  },
+  plugins: ['hyper-material-theme'],
+};
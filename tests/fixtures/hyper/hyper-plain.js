@@ -0,0 +1,8 @@
+module.exports = {
+  config: {
+    fontSize: 13,
+    fontFamily: "\"Fira Code\", monospace",
+    shell: '/usr/bin/zsh',
+  },
+  plugins: [],
+};
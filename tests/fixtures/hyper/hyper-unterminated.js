@@ -0,0 +1,5 @@
+module.exports = {
+  config: {
+    fontFamily: 'Hack Nerd Font,
+  },
+};
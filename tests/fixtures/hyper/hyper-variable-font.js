@@ -0,0 +1,9 @@
+const { homedir } = require('os');
+const nerdFont = 'JetBrainsMono Nerd Font';
+
+module.exports = {
+  config: {
+    fontSize: 13,
+    fontFamily: nerdFont + ', monospace',
+  },
+};
@@ -0,0 +1,10 @@
+[Appearance]
+ColorScheme=BlackOnWhite
+Font=Noto Sans Mono,11,-1,5,400,0,0,0,0,0,0,0,0,0,0,1,Regular
+
+[General]
+Name=Work
+Parent=FALLBACK/
+
+[Scrolling]
+HistorySize=10000
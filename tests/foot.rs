@@ -0,0 +1,115 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+fn run_foot(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    let config_dirs = home.join("etc/xdg").to_string_lossy().to_string();
+
+    support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("XDG_CONFIG_DIRS", &config_dirs),
+            ("TERM", "foot"),
+        ],
+        None,
+    )
+}
+
+#[test]
+fn foot_nerd_font_in_fallback_list() {
+    let home = support::scenario_home("foot-nerd-fallback");
+    support::install_config_fixture(
+        &home,
+        "foot",
+        "foot-nerd-fallback.ini",
+        ".config/foot/foot.ini",
+    );
+
+    let output = run_foot(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "foot_nerd_fallback_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("foot_nerd_fallback_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn foot_font_from_include() {
+    let home = support::scenario_home("foot-include");
+    support::install_config_fixture(&home, "foot", "foot-include.ini", ".config/foot/foot.ini");
+    support::install_config_fixture(&home, "foot", "fonts.ini", ".config/foot/fonts.ini");
+
+    let output = run_foot(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("foot_include_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn foot_plain_font() {
+    let home = support::scenario_home("foot-plain");
+    support::install_config_fixture(&home, "foot", "foot-plain.ini", ".config/foot/foot.ini");
+
+    let output = run_foot(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("foot_plain_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn foot_system_config() {
+    let home = support::scenario_home("foot-system-config");
+    support::install_config_fixture(
+        &home,
+        "foot",
+        "foot-nerd-fallback.ini",
+        "etc/xdg/foot/foot.ini",
+    );
+
+    let output = run_foot(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "foot_system_config_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn foot_user_config_shadows_system_config() {
+    let home = support::scenario_home("foot-user-shadows-system");
+    support::install_config_fixture(&home, "foot", "foot-default.ini", ".config/foot/foot.ini");
+    support::install_config_fixture(
+        &home,
+        "foot",
+        "foot-nerd-fallback.ini",
+        "etc/xdg/foot/foot.ini",
+    );
+
+    let output = run_foot(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "foot_user_shadows_system_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn foot_without_config_uses_default_font() {
+    let home = support::scenario_home("foot-no-config");
+
+    let output = run_foot(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "foot_no_config_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,127 @@
+mod support;
+
+use insta::assert_snapshot;
+
+fn run_gnome_terminal(scenario: &str, fixture: Option<&str>) -> std::process::Output {
+    let home = support::scenario_home(scenario);
+    if let Some(fixture) = fixture {
+        support::install_dconf_fixture(&home, fixture);
+    }
+    let home_str = home.to_string_lossy().to_string();
+
+    support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("VTE_VERSION", "7600"),
+            (
+                "GNOME_TERMINAL_SCREEN",
+                "/org/gnome/Terminal/screen/0d3c2b7a",
+            ),
+        ],
+        None,
+    )
+}
+
+#[test]
+fn gnome_terminal_default_profile_nerd_font() {
+    let output = run_gnome_terminal(
+        "gnome-terminal-nerd-font",
+        Some("gnome-terminal-nerd-font.gvdb"),
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "gnome_terminal_nerd_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "gnome_terminal_nerd_font_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn gnome_terminal_system_font() {
+    let output = run_gnome_terminal(
+        "gnome-terminal-system-font",
+        Some("gnome-terminal-system-font.gvdb"),
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "gnome_terminal_system_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn gnome_terminal_unset_keys_use_defaults() {
+    let output = run_gnome_terminal("gnome-terminal-defaults", Some("dconf-defaults.gvdb"));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "gnome_terminal_defaults_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn gnome_terminal_malformed_database() {
+    let output = run_gnome_terminal("gnome-terminal-malformed", Some("dconf-malformed.gvdb"));
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "gnome_terminal_malformed_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "gnome_terminal_malformed_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn gnome_terminal_without_database() {
+    let output = run_gnome_terminal("gnome-terminal-no-database", None);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "gnome_terminal_no_database_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn ptyxis_custom_font() {
+    let home = support::scenario_home("ptyxis-custom-font");
+    support::install_dconf_fixture(&home, "ptyxis-custom-font.gvdb");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[
+            ("HOME", &home_str),
+            ("VTE_VERSION", "7800"),
+            ("PTYXIS_VERSION", "47.0"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "ptyxis_custom_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn other_vte_terminal_has_no_resolver() {
+    let output = support::run_cli(&["--json"], &[("VTE_VERSION", "7600")], None);
+
+    assert_eq!(output.status.code(), Some(4));
+    assert_snapshot!(
+        "vte_no_resolver_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,114 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+const APP_SUPPORT_NORMALIZATIONS: &[(&str, &str)] = if cfg!(target_os = "macos") {
+    &[("Library/Application Support", "<APP_SUPPORT>")]
+} else {
+    &[(".config", "<APP_SUPPORT>")]
+};
+
+fn run_hyper(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+
+    support::run_cli(
+        &["--json", "--explain"],
+        &[("HOME", &home_str), ("TERM_PROGRAM", "Hyper")],
+        None,
+    )
+}
+
+fn json_snapshot(output: &std::process::Output) -> String {
+    support::stdout_json_snapshot_with_extra_normalizations(output, APP_SUPPORT_NORMALIZATIONS)
+}
+
+#[test]
+fn hyper_js_nerd_font_in_family_list() {
+    let home = support::scenario_home("hyper-nerd-font");
+    support::install_app_config_fixture(&home, "hyper", "hyper-nerd-font.js", "Hyper/.hyper.js");
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("hyper_nerd_font_json", json_snapshot(&output));
+    assert_snapshot!("hyper_nerd_font_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn hyper_legacy_home_config() {
+    let home = support::scenario_home("hyper-legacy");
+    support::install_config_fixture(&home, "hyper", "hyper-plain.js", ".hyper.js");
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("hyper_legacy_json", json_snapshot(&output));
+}
+
+#[test]
+fn hyper_js_config_assigned_to_exports() {
+    let home = support::scenario_home("hyper-exports-config");
+    support::install_app_config_fixture(
+        &home,
+        "hyper",
+        "hyper-exports-config.js",
+        "Hyper/.hyper.js",
+    );
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("hyper_exports_config_json", json_snapshot(&output));
+}
+
+#[test]
+fn hyper_json_config_wins() {
+    let home = support::scenario_home("hyper-json");
+    support::install_app_config_fixture(&home, "hyper", "hyper.json", "Hyper/hyper.json");
+    support::install_app_config_fixture(&home, "hyper", "hyper-plain.js", "Hyper/.hyper.js");
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("hyper_json_json", json_snapshot(&output));
+}
+
+#[test]
+fn hyper_missing_font_family_uses_default() {
+    let home = support::scenario_home("hyper-no-font");
+    support::install_app_config_fixture(&home, "hyper", "hyper-no-font.js", "Hyper/.hyper.js");
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("hyper_no_font_json", json_snapshot(&output));
+}
+
+#[test]
+fn hyper_non_literal_font_family_is_config_error() {
+    let home = support::scenario_home("hyper-variable-font");
+    support::install_app_config_fixture(
+        &home,
+        "hyper",
+        "hyper-variable-font.js",
+        "Hyper/.hyper.js",
+    );
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!("hyper_variable_font_json", json_snapshot(&output));
+}
+
+#[test]
+fn hyper_unterminated_string_is_config_error() {
+    let home = support::scenario_home("hyper-unterminated");
+    support::install_config_fixture(&home, "hyper", "hyper-unterminated.js", ".hyper.js");
+
+    let output = run_hyper(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!("hyper_unterminated_json", json_snapshot(&output));
+}
@@ -0,0 +1,132 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+const APP_SUPPORT_NORMALIZATIONS: &[(&str, &str)] = if cfg!(target_os = "macos") {
+    &[("Library/Application Support", "<APP_SUPPORT>")]
+} else {
+    &[(".config", "<APP_SUPPORT>")]
+};
+
+fn run_jetbrains(home: &Path, extra: &[(&str, &str)]) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    let mut env = vec![
+        ("HOME", home_str.as_str()),
+        ("TERMINAL_EMULATOR", "JetBrains-JediTerm"),
+    ];
+    env.extend_from_slice(extra);
+
+    support::run_cli(&["--json", "--explain"], &env, None)
+}
+
+fn json_snapshot(output: &std::process::Output) -> String {
+    support::stdout_json_snapshot_with_extra_normalizations(output, APP_SUPPORT_NORMALIZATIONS)
+}
+
+fn install_two_products(home: &Path) {
+    support::install_app_config_fixture(
+        home,
+        "jetbrains",
+        "terminal-font-nerd.xml",
+        "JetBrains/PyCharm2023.3/options/terminal-font.xml",
+    );
+    support::install_app_config_fixture(
+        home,
+        "jetbrains",
+        "console-font-plain.xml",
+        "JetBrains/IntelliJIdea2024.2/options/console-font.xml",
+    );
+}
+
+#[test]
+fn jetbrains_newest_product_dir() {
+    let home = support::scenario_home("jetbrains-newest");
+    install_two_products(&home);
+
+    let output = run_jetbrains(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("jetbrains_newest_json", json_snapshot(&output));
+    assert_snapshot!("jetbrains_newest_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn jetbrains_product_dir_from_history_file() {
+    let home = support::scenario_home("jetbrains-inferred");
+    install_two_products(&home);
+    let history = home
+        .join(".cache/JetBrains/PyCharm2023.3/terminal/history/history-2")
+        .to_string_lossy()
+        .to_string();
+
+    let output = run_jetbrains(&home, &[("__INTELLIJ_COMMAND_HISTFILE__", &history)]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("jetbrains_inferred_json", json_snapshot(&output));
+}
+
+#[test]
+fn jetbrains_editor_font_fallback() {
+    let home = support::scenario_home("jetbrains-editor-font");
+    support::install_app_config_fixture(
+        &home,
+        "jetbrains",
+        "editor-font-fallback.xml",
+        "JetBrains/RustRover2024.3/options/editor-font.xml",
+    );
+
+    let output = run_jetbrains(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("jetbrains_editor_font_json", json_snapshot(&output));
+}
+
+#[test]
+fn jetbrains_android_studio() {
+    let home = support::scenario_home("jetbrains-android-studio");
+    support::install_app_config_fixture(
+        &home,
+        "jetbrains",
+        "console-font-plain.xml",
+        "JetBrains/GoLand2023.1/options/console-font.xml",
+    );
+    support::install_app_config_fixture(
+        &home,
+        "jetbrains",
+        "terminal-font-nerd.xml",
+        "Google/AndroidStudio2024.1/options/terminal-font.xml",
+    );
+
+    let output = run_jetbrains(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("jetbrains_android_studio_json", json_snapshot(&output));
+}
+
+#[test]
+fn jetbrains_without_font_settings_uses_default() {
+    let home = support::scenario_home("jetbrains-default");
+    support::install_app_config_fixture(
+        &home,
+        "jetbrains",
+        "other-options.xml",
+        "JetBrains/WebStorm2024.1/options/other.xml",
+    );
+
+    let output = run_jetbrains(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("jetbrains_default_json", json_snapshot(&output));
+}
+
+#[test]
+fn jetbrains_without_config_dir() {
+    let home = support::scenario_home("jetbrains-no-config");
+
+    let output = run_jetbrains(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!("jetbrains_no_config_json", json_snapshot(&output));
+}
@@ -0,0 +1,182 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+fn run_konsole(home: &Path, extra: &[(&str, &str)]) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    let data_dirs = home.join("system-share").to_string_lossy().to_string();
+    let mut env = vec![
+        ("HOME", home_str.as_str()),
+        ("XDG_DATA_DIRS", data_dirs.as_str()),
+        ("KONSOLE_VERSION", "240202"),
+    ];
+    env.extend_from_slice(extra);
+
+    support::run_cli(&["--json", "--explain"], &env, None)
+}
+
+#[test]
+fn konsole_profile_from_env() {
+    let home = support::scenario_home("konsole-profile-env");
+    support::install_config_fixture(
+        &home,
+        "konsole",
+        "hack-nerd-font.profile",
+        ".local/share/konsole/Hack.profile",
+    );
+    support::install_config_fixture(&home, "konsole", "konsolerc", ".config/konsolerc");
+
+    let output = run_konsole(&home, &[("KONSOLE_PROFILE_NAME", "Hack")]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "konsole_profile_env_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("konsole_profile_env_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn konsole_default_profile_from_konsolerc() {
+    let home = support::scenario_home("konsole-default-profile");
+    support::install_config_fixture(
+        &home,
+        "konsole",
+        "work-plain.profile",
+        ".local/share/konsole/Work.profile",
+    );
+    support::install_config_fixture(&home, "konsole", "konsolerc", ".config/konsolerc");
+
+    let output = run_konsole(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "konsole_default_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn konsole_renamed_profile_matches_name() {
+    let home = support::scenario_home("konsole-renamed-profile");
+    support::install_config_fixture(
+        &home,
+        "konsole",
+        "work-plain.profile",
+        ".local/share/konsole/Profile 1.profile",
+    );
+
+    let output = run_konsole(&home, &[("KONSOLE_PROFILE_NAME", "Work")]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "konsole_renamed_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn konsole_profile_inherits_font_from_parent() {
+    let home = support::scenario_home("konsole-parent-profile");
+    support::install_config_fixture(
+        &home,
+        "konsole",
+        "hack-nerd-font.profile",
+        "system-share/konsole/Hack.profile",
+    );
+    let parent = home.join("system-share/konsole/Hack.profile");
+    let child = home.join(".local/share/konsole/Child.profile");
+    std::fs::create_dir_all(child.parent().expect("profile should have parent"))
+        .expect("failed to create profile directory");
+    std::fs::write(
+        &child,
+        format!(
+            "[Appearance]\nColorScheme=Solarized\n\n[General]\nName=Child\nParent={}\n",
+            parent.display()
+        ),
+    )
+    .expect("failed to write child profile");
+
+    let output = run_konsole(&home, &[("KONSOLE_PROFILE_NAME", "Child")]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "konsole_parent_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn konsole_missing_parent_profile_uses_builtin_font() {
+    let home = support::scenario_home("konsole-missing-parent-profile");
+    let parent = home.join("system-share/konsole/Gone.profile");
+    let child = home.join(".local/share/konsole/Child.profile");
+    std::fs::create_dir_all(child.parent().expect("profile should have parent"))
+        .expect("failed to create profile directory");
+    std::fs::write(
+        &child,
+        format!(
+            "[Appearance]\nColorScheme=Solarized\n\n[General]\nName=Child\nParent={}\n",
+            parent.display()
+        ),
+    )
+    .expect("failed to write child profile");
+
+    let output = run_konsole(&home, &[("KONSOLE_PROFILE_NAME", "Child")]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "konsole_missing_parent_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn konsole_builtin_profile_uses_system_fixed_font() {
+    let home = support::scenario_home("konsole-builtin-profile");
+    support::install_config_fixture(&home, "konsole", "kdeglobals", ".config/kdeglobals");
+
+    let output = run_konsole(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "konsole_builtin_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn konsole_missing_profile() {
+    let home = support::scenario_home("konsole-missing-profile");
+
+    let output = run_konsole(&home, &[("KONSOLE_PROFILE_NAME", "Gone")]);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "konsole_missing_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn yakuake_default_profile_from_yakuakerc() {
+    let home = support::scenario_home("yakuake-default-profile");
+    support::install_config_fixture(
+        &home,
+        "konsole",
+        "hack-nerd-font.profile",
+        ".local/share/konsole/Hack.profile",
+    );
+    support::install_config_fixture(&home, "konsole", "konsolerc", ".config/konsolerc");
+    support::install_config_fixture(&home, "konsole", "yakuakerc", ".config/yakuakerc");
+
+    let output = run_konsole(&home, &[("KONSOLE_DBUS_SERVICE", "org.kde.yakuake")]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "yakuake_default_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
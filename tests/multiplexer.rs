@@ -0,0 +1,290 @@
+mod support;
+
+use has_nerd_font::{DetectOptions, Terminal, detect_with};
+use insta::assert_snapshot;
+
+/// A stub `tmux` that answers `display-message` and `show-environment` the
+/// way a server with one attached client would.
+fn install_tmux_stub(home: &std::path::Path, client: &str, environment: &str) -> String {
+    let script = format!(
+        "case \"$1\" in\n  display-message) printf '%b\\n' '{client}' ;;\n  show-environment) printf '%b' '{environment}' ;;\n  *) exit 1 ;;\nesac\n"
+    );
+    let bin = support::install_stub_command(home, "tmux", &script);
+    bin.to_string_lossy().to_string()
+}
+
+#[test]
+fn tmux_client_termname_identifies_outer_terminal() {
+    let home = support::scenario_home("tmux-kitty");
+    let path = install_tmux_stub(&home, "0\\txterm-kitty\\t", "");
+
+    let output = support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("PATH", &path),
+            ("TMUX", "/tmp/tmux-1000/default,1234,0"),
+            ("TERM_PROGRAM", "tmux"),
+            ("TERM", "tmux-256color"),
+            // Left over from the terminal that started the tmux server.
+            ("ALACRITTY_LOG", "/tmp/Alacritty-1.log"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("tmux_kitty_json", support::stdout_json_snapshot(&output));
+    assert_snapshot!("tmux_kitty_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn tmux_client_termtype_identifies_outer_terminal() {
+    let home = support::scenario_home("tmux-wezterm");
+    let path = install_tmux_stub(
+        &home,
+        "0\\txterm-256color\\tWezTerm 20240203-110809-5046fc22",
+        "",
+    );
+
+    let output = support::run_cli(
+        &["--json"],
+        &[
+            ("PATH", &path),
+            ("TMUX", "/tmp/tmux-1000/default,1234,0"),
+            ("TERM_PROGRAM", "tmux"),
+            ("TERM", "tmux-256color"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("tmux_wezterm_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn tmux_show_environment_feeds_outer_terminal_config() {
+    let home = support::scenario_home("tmux-alacritty");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let home_str = home.to_string_lossy().to_string();
+    let path = install_tmux_stub(
+        &home,
+        "0\\txterm-256color\\t",
+        "-DISPLAY\\nALACRITTY_LOG=/tmp/Alacritty-2.log\\nSSH_AUTH_SOCK=/tmp/agent\\n",
+    );
+
+    let output = support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("PATH", &path),
+            ("HOME", &home_str),
+            ("TMUX", "/tmp/tmux-1000/default,1234,0"),
+            ("TERM_PROGRAM", "tmux"),
+            ("TERM_PROGRAM_VERSION", "3.4"),
+            ("TERM", "tmux-256color"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "tmux_alacritty_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("tmux_alacritty_explain", support::stderr_text(&output));
+}
+
+fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// alacritty (40) -> zsh (50) -> <client> (60), and a daemonized server (100)
+/// -> bash (110) -> has-nerd-font (120).
+fn detached_multiplexer_tree(home: &std::path::Path, name: &str) -> support::FakeProc {
+    let proc = support::FakeProc::new(home, 120);
+    proc.add(1, 0, "systemd", None);
+    proc.add(40, 1, "alacritty", Some("/usr/bin/alacritty"));
+    proc.add(50, 40, "zsh", Some("/usr/bin/zsh"));
+    proc.add(60, 50, name, Some(&format!("/usr/bin/{name}")));
+    proc.add(100, 1, name, Some(&format!("/usr/bin/{name}")));
+    proc.add(110, 100, "bash", Some("/usr/bin/bash"));
+    proc.add(120, 110, "has-nerd-font", None);
+    proc
+}
+
+#[test]
+fn screen_client_environment_identifies_outer_terminal() {
+    let home = support::scenario_home("screen-alacritty");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let home_str = home.to_string_lossy().to_string();
+    let proc = detached_multiplexer_tree(&home, "screen");
+    proc.set_cmdline(60, &["screen", "-r", "pts-0.devbox"]);
+    proc.set_environ(
+        60,
+        &[
+            ("HOME", &home_str),
+            ("ALACRITTY_LOG", "/tmp/Alacritty-3.log"),
+        ],
+    );
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(
+        &vars(&[
+            ("STY", "4242.pts-0.devbox"),
+            ("TERM", "screen.xterm-256color"),
+            ("TERM_PROGRAM", "CoolNewTerm"),
+        ]),
+        &options,
+    );
+
+    assert_snapshot!(
+        "screen_alacritty_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn zellij_client_process_tree_identifies_outer_terminal() {
+    let home = support::scenario_home("zellij-alacritty");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let home_str = home.to_string_lossy().to_string();
+    let proc = detached_multiplexer_tree(&home, "zellij");
+    proc.set_cmdline(60, &["zellij", "attach", "devbox"]);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(
+        &vars(&[
+            ("ZELLIJ", "0"),
+            ("ZELLIJ_SESSION_NAME", "devbox"),
+            ("HOME", &home_str),
+            ("TERM", "xterm-256color"),
+        ]),
+        &options,
+    );
+
+    assert_snapshot!(
+        "zellij_alacritty_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+/// Adds another screen client, run from kitty (200) -> zsh (210) -> screen
+/// (220), with the given owner and command line.
+fn add_other_screen_client(proc: &support::FakeProc, uid: u32, args: &[&str]) {
+    for (pid, ppid, name) in [(200, 1, "kitty"), (210, 200, "zsh"), (220, 210, "screen")] {
+        proc.add(pid, ppid, name, Some(&format!("/usr/bin/{name}")));
+        proc.set_uids(pid, uid, uid);
+    }
+    proc.set_cmdline(220, args);
+    proc.set_environ(220, &[("KITTY_WINDOW_ID", "1"), ("TERM", "xterm-kitty")]);
+}
+
+fn screen_result(
+    proc: &support::FakeProc,
+    home: &std::path::Path,
+) -> has_nerd_font::DetectionResult {
+    let home_str = home.to_string_lossy().to_string();
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    detect_with(
+        &vars(&[
+            ("STY", "4242.pts-0.devbox"),
+            ("HOME", &home_str),
+            ("TERM", "screen.xterm-256color"),
+        ]),
+        &options,
+    )
+}
+
+#[test]
+fn screen_ignores_other_users_clients() {
+    let home = support::scenario_home("screen-other-user");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let proc = detached_multiplexer_tree(&home, "screen");
+    proc.set_cmdline(60, &["screen", "-r", "pts-0.devbox"]);
+    add_other_screen_client(&proc, 1001, &["screen", "-x", "4242.pts-0.devbox"]);
+
+    let result = screen_result(&proc, &home);
+
+    assert_eq!(result.terminal, Some(Terminal::Alacritty));
+}
+
+#[test]
+fn screen_ignores_clients_of_other_sessions() {
+    let home = support::scenario_home("screen-other-session");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let proc = detached_multiplexer_tree(&home, "screen");
+    proc.set_cmdline(60, &["screen", "-dr", "4242"]);
+    add_other_screen_client(&proc, 1000, &["screen", "-r", "5151.pts-1.devbox"]);
+
+    let result = screen_result(&proc, &home);
+
+    assert_eq!(result.terminal, Some(Terminal::Alacritty));
+}
+
+#[test]
+fn screen_server_parent_is_the_client() {
+    let home = support::scenario_home("screen-server-parent");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    // A session started by a plain `screen` that is still attached, so the
+    // server's parent is the client.
+    let proc = support::FakeProc::new(&home, 120);
+    proc.add(1, 0, "systemd", None);
+    proc.add(40, 1, "alacritty", Some("/usr/bin/alacritty"));
+    proc.add(50, 40, "zsh", Some("/usr/bin/zsh"));
+    proc.add(60, 50, "screen", Some("/usr/bin/screen"));
+    proc.add(100, 60, "screen", Some("/usr/bin/screen"));
+    proc.add(110, 100, "bash", Some("/usr/bin/bash"));
+    proc.add(120, 110, "has-nerd-font", None);
+    proc.set_cmdline(60, &["screen"]);
+    add_other_screen_client(&proc, 1000, &["screen", "-S", "other"]);
+
+    let result = screen_result(&proc, &home);
+
+    assert_eq!(result.terminal, Some(Terminal::Alacritty));
+}
+
+#[test]
+fn screen_ambiguous_clients_are_inconclusive() {
+    let home = support::scenario_home("screen-ambiguous");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let proc = detached_multiplexer_tree(&home, "screen");
+    proc.set_cmdline(60, &["screen", "-x", "pts-0.devbox"]);
+    add_other_screen_client(&proc, 1000, &["screen", "-x", "pts-0.devbox"]);
+
+    let result = screen_result(&proc, &home);
+
+    assert_snapshot!(
+        "screen_ambiguous_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn zellij_ignores_clients_of_other_sessions() {
+    let home = support::scenario_home("zellij-other-session");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let home_str = home.to_string_lossy().to_string();
+    let proc = detached_multiplexer_tree(&home, "zellij");
+    proc.set_environ(
+        60,
+        &[("HOME", &home_str), ("ZELLIJ_SESSION_NAME", "devbox")],
+    );
+    for (pid, ppid, name) in [(200, 1, "kitty"), (210, 200, "zsh"), (220, 210, "zellij")] {
+        proc.add(pid, ppid, name, Some(&format!("/usr/bin/{name}")));
+    }
+    proc.set_cmdline(220, &["zellij", "--session", "scratch"]);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(
+        &vars(&[
+            ("ZELLIJ", "0"),
+            ("ZELLIJ_SESSION_NAME", "devbox"),
+            ("HOME", &home_str),
+            ("TERM", "xterm-256color"),
+        ]),
+        &options,
+    );
+
+    assert_eq!(result.terminal, Some(Terminal::Alacritty));
+}
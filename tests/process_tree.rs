@@ -0,0 +1,112 @@
+mod support;
+
+use has_nerd_font::{DetectOptions, detect_with};
+use insta::assert_snapshot;
+
+fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn process_tree_finds_alacritty_and_resolves_config() {
+    let home = support::scenario_home("process-tree-alacritty");
+    support::install_alacritty_fixture(&home, "alacritty-nerd-font.toml");
+    let home_str = home.to_string_lossy().to_string();
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(1, 0, "systemd", Some("/usr/lib/systemd/systemd"));
+    proc.add(100, 1, "alacritty", Some("/usr/bin/alacritty"));
+    proc.add(200, 100, "sudo", None);
+    proc.add(300, 200, "has-nerd-font", Some("/usr/bin/has-nerd-font"));
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(&vars(&[("HOME", &home_str)]), &options);
+
+    assert_snapshot!(
+        "process_tree_alacritty_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn process_tree_uses_exe_name_over_truncated_comm() {
+    let home = support::scenario_home("process-tree-wezterm");
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(100, 1, "wezterm-gui", Some("/opt/wezterm/wezterm-gui"));
+    proc.add(200, 100, "(bash) weird)", Some("/usr/bin/bash"));
+    proc.add(300, 200, "has-nerd-font", None);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(&[], &options);
+
+    assert_snapshot!(
+        "process_tree_wezterm_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn process_tree_without_known_emulator_is_unknown() {
+    let home = support::scenario_home("process-tree-unknown");
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(1, 0, "systemd", None);
+    proc.add(100, 1, "cron", Some("/usr/sbin/cron"));
+    proc.add(300, 100, "has-nerd-font", None);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(&[], &options);
+
+    assert_snapshot!(
+        "process_tree_unknown_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn env_identification_takes_precedence_over_process_tree() {
+    let home = support::scenario_home("process-tree-env-wins");
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(100, 1, "kitty", Some("/usr/bin/kitty"));
+    proc.add(300, 100, "has-nerd-font", None);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(&vars(&[("TERM_PROGRAM", "CoolNewTerm")]), &options);
+
+    assert_snapshot!(
+        "process_tree_env_wins_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn process_tree_names_vte_terminal() {
+    let home = support::scenario_home("process-tree-xfce4-terminal");
+    support::install_xfconf_fixture(&home, "xfce4-terminal-nerd-font.xml", "xfce4-terminal");
+    let home_str = home.to_string_lossy().to_string();
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(100, 1, "xfce4-terminal", Some("/usr/bin/xfce4-terminal"));
+    proc.add(200, 100, "bash", Some("/usr/bin/bash"));
+    proc.add(300, 200, "has-nerd-font", None);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let result = detect_with(
+        &vars(&[
+            ("HOME", &home_str),
+            ("COLORTERM", "truecolor"),
+            ("VTE_VERSION", "7600"),
+        ]),
+        &options,
+    );
+
+    assert_snapshot!(
+        "process_tree_xfce4_terminal_json",
+        support::result_json_snapshot(&result)
+    );
+}
@@ -0,0 +1,153 @@
+mod support;
+
+use insta::assert_snapshot;
+
+const LAPTOP: &str = "10.0.0.2 51234 10.0.0.1 22";
+const DESKTOP: &str = "10.0.0.3 40022 10.0.0.1 22";
+
+#[test]
+fn remembered_answer_is_used_for_same_client() {
+    let home = support::scenario_home("remote-hints-same-client");
+    let home_str = home.to_string_lossy().to_string();
+
+    let remember = support::run_cli(
+        &["remember", "yes"],
+        &[
+            ("HOME", &home_str),
+            ("SSH_CONNECTION", LAPTOP),
+            ("TERM_PROGRAM", "Apple_Terminal"),
+        ],
+        None,
+    );
+    assert_eq!(remember.status.code(), Some(0));
+
+    let output = support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("SSH_CONNECTION", "10.0.0.2 60001 10.0.0.1 22"),
+            ("TERM_PROGRAM", "Apple_Terminal"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "remote_hints_same_client_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "remote_hints_same_client_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn remembered_answer_is_keyed_by_client() {
+    let home = support::scenario_home("remote-hints-other-client");
+    let state = home.join("state").to_string_lossy().to_string();
+
+    let remember = support::run_cli(
+        &["remember", "no"],
+        &[
+            ("XDG_STATE_HOME", &state),
+            ("SSH_CLIENT", "10.0.0.2 51234 22"),
+        ],
+        None,
+    );
+    assert_eq!(remember.status.code(), Some(0));
+    assert!(home.join("state/has-nerd-font/remote-hints.json").is_file());
+
+    let same = support::run_cli(
+        &[],
+        &[
+            ("XDG_STATE_HOME", &state),
+            ("SSH_CONNECTION", LAPTOP),
+            ("TERM_PROGRAM", "Apple_Terminal"),
+        ],
+        None,
+    );
+    assert_eq!(same.status.code(), Some(1));
+
+    let other = support::run_cli(
+        &["--json"],
+        &[
+            ("XDG_STATE_HOME", &state),
+            ("SSH_CONNECTION", DESKTOP),
+            ("TERM_PROGRAM", "Apple_Terminal"),
+        ],
+        None,
+    );
+    assert_eq!(other.status.code(), Some(3));
+    assert_snapshot!(
+        "remote_hints_other_client_json",
+        support::stdout_json_snapshot(&other)
+    );
+}
+
+#[test]
+fn remembered_answer_expires() {
+    let home = support::scenario_home("remote-hints-expired");
+    let store = home.join(".local/state/has-nerd-font");
+    std::fs::create_dir_all(&store).expect("failed to create state dir");
+    std::fs::write(
+        store.join("remote-hints.json"),
+        r#"{ "10.0.0.2": { "detected": true, "saved_at": 1700000000 } }"#,
+    )
+    .expect("failed to write remote hints");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[
+            ("HOME", &home_str),
+            ("SSH_CONNECTION", LAPTOP),
+            ("TERM_PROGRAM", "Apple_Terminal"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_snapshot!(
+        "remote_hints_expired_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn remember_requires_ssh_session() {
+    let home = support::scenario_home("remote-hints-local");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["remember", "yes"], &[("HOME", &home_str)], None);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_snapshot!("remote_hints_local_stderr", support::stderr_text(&output));
+}
+
+#[test]
+fn remember_keeps_unreadable_store() {
+    let home = support::scenario_home("remote-hints-corrupt");
+    let store = home.join(".local/state/has-nerd-font/remote-hints.json");
+    std::fs::create_dir_all(store.parent().expect("store should have a parent"))
+        .expect("failed to create state dir");
+    let corrupt = r#"{ "10.0.0.3": { "detected": true, "saved_at": "#;
+    std::fs::write(&store, corrupt).expect("failed to write remote hints");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["remember", "yes"],
+        &[("HOME", &home_str), ("SSH_CONNECTION", LAPTOP)],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        std::fs::read_to_string(&store).expect("store should still exist"),
+        corrupt
+    );
+    let entries: Vec<_> = std::fs::read_dir(store.parent().expect("store should have a parent"))
+        .expect("failed to list state dir")
+        .collect();
+    assert_eq!(entries.len(), 1, "no temporary file should be left behind");
+}
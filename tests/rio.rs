@@ -0,0 +1,100 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+const CONFIG: &str = ".config/rio/config.toml";
+
+fn run_rio(home: &Path, extra: &[(&str, &str)]) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    let mut env = vec![("HOME", home_str.as_str()), ("TERM_PROGRAM", "rio")];
+    env.extend_from_slice(extra);
+
+    support::run_cli(&["--json", "--explain"], &env, None)
+}
+
+#[test]
+fn rio_nerd_font() {
+    let home = support::scenario_home("rio-nerd-font");
+    support::install_config_fixture(&home, "rio", "config-nerd-font.toml", CONFIG);
+
+    let output = run_rio(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("rio_nerd_font_json", support::stdout_json_snapshot(&output));
+    assert_snapshot!("rio_nerd_font_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn rio_extras_count() {
+    let home = support::scenario_home("rio-extras");
+    support::install_config_fixture(&home, "rio", "config-extras.toml", CONFIG);
+
+    let output = run_rio(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("rio_extras_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn rio_regular_family_overrides_family() {
+    let home = support::scenario_home("rio-regular");
+    support::install_config_fixture(&home, "rio", "config-regular.toml", CONFIG);
+
+    let output = run_rio(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("rio_regular_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn rio_without_font_uses_default() {
+    let home = support::scenario_home("rio-no-font");
+    support::install_config_fixture(&home, "rio", "config-no-font.toml", CONFIG);
+
+    let output = run_rio(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("rio_no_font_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn rio_detected_from_term() {
+    let home = support::scenario_home("rio-term");
+    support::install_config_fixture(
+        &home,
+        "rio",
+        "config-nerd-font.toml",
+        "rio-config/config.toml",
+    );
+    let config_home = home.join("rio-config").to_string_lossy().to_string();
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[
+            ("HOME", &home_str),
+            ("TERM", "rio"),
+            ("RIO_CONFIG_HOME", &config_home),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "rio_config_home_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn rio_malformed_config() {
+    let home = support::scenario_home("rio-malformed");
+    support::install_config_fixture(&home, "rio", "config-malformed.toml", CONFIG);
+
+    let output = run_rio(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!("rio_malformed_json", support::stdout_json_snapshot(&output));
+}
@@ -20,3 +20,90 @@ fn ssh_session_snapshots_json_and_explain() {
     );
     assert_snapshot!("ssh_remote_session_explain", support::stderr_text(&output));
 }
+
+#[test]
+fn ssh_session_honours_forwarded_answer() {
+    let output = support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.1 22"),
+            ("LC_NERD_FONT", "1"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "ssh_forwarded_answer_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "ssh_forwarded_answer_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn forwarded_answer_is_ignored_outside_ssh() {
+    let output = support::run_cli(
+        &["--json"],
+        &[("TERM_PROGRAM", "ghostty"), ("LC_NERD_FONT", "0")],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "ssh_forwarded_answer_local_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn ssh_session_identifies_client_from_lc_terminal() {
+    let output = support::run_cli(
+        &["--json"],
+        &[
+            ("SSH_TTY", "/dev/pts/1"),
+            ("LC_TERMINAL", "iTerm2"),
+            ("LC_TERMINAL_VERSION", "3.5.4"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_snapshot!(
+        "ssh_lc_terminal_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn export_prints_ssh_config() {
+    let output = support::run_cli(&["export"], &[("TERM_PROGRAM", "ghostty")], None);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("ssh_export_enabled", support::stdout_text(&output));
+}
+
+#[test]
+fn export_forwards_explicit_disable() {
+    let output = support::run_cli(&["export"], &[("NERD_FONT", "0")], None);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("ssh_export_disabled", support::stdout_text(&output));
+}
+
+#[test]
+fn export_omits_answer_when_inconclusive() {
+    let output = support::run_cli(
+        &["export"],
+        &[
+            ("TERM_PROGRAM", "Apple_Terminal"),
+            ("SSH_TTY", "/dev/pts/1"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("ssh_export_inconclusive", support::stdout_text(&output));
+}
@@ -36,16 +36,23 @@ pub fn stdout_json_snapshot_with_extra_normalizations(
     output: &Output,
     extra: &[(&str, &str)],
 ) -> String {
-    let mut json: Value =
-        serde_json::from_slice(&output.stdout).expect("stdout should be valid JSON");
+    let json: Value = serde_json::from_slice(&output.stdout).expect("stdout should be valid JSON");
+    normalized_json_snapshot(json, extra)
+}
+
+pub fn result_json_snapshot(result: &has_nerd_font::DetectionResult) -> String {
+    let json = serde_json::to_value(result).expect("failed to serialize result as json");
+    normalized_json_snapshot(json, &[])
+}
 
+fn normalized_json_snapshot(mut json: Value, extra: &[(&str, &str)]) -> String {
     let scenario_root = snapshot_root();
     let scenario_root_str = scenario_root.to_string_lossy().to_string();
     let scenario_root_canonical = scenario_root
         .canonicalize()
         .map(|p| p.to_string_lossy().to_string());
 
-    for key in &["config_path", "error_reason"] {
+    for key in &["config_path", "error_reason", "font"] {
         if let Some(field) = json.get_mut(*key)
             && let Some(value) = field.as_str()
         {
@@ -135,18 +142,26 @@ pub fn install_iterm2_fixture(home: &Path, fixture_name: &str) {
     std::fs::copy(&fixture_path, &plist_path).expect("failed to copy iTerm2 plist fixture");
 }
 
+/// Returns VS Code's `User` directory for `app_dir` (e.g. `Code`) under `home`.
+pub fn vscode_user_dir(home: &Path, app_dir: &str) -> PathBuf {
+    if cfg!(target_os = "macos") {
+        home.join(format!("Library/Application Support/{app_dir}/User"))
+    } else {
+        home.join(format!(".config/{app_dir}/User"))
+    }
+}
+
 pub fn install_vscode_fixture(home: &Path, fixture_name: &str, app_dir: &str) {
+    install_vscode_user_fixture(home, fixture_name, app_dir, "settings.json");
+}
+
+/// Copies a VS Code fixture to `dest`, relative to the `User` directory.
+pub fn install_vscode_user_fixture(home: &Path, fixture_name: &str, app_dir: &str, dest: &str) {
     let fixture_path = Path::new("tests")
         .join("fixtures")
         .join("vscode")
         .join(fixture_name);
-    let settings_path = if cfg!(target_os = "macos") {
-        home.join(format!(
-            "Library/Application Support/{app_dir}/User/settings.json"
-        ))
-    } else {
-        home.join(format!(".config/{app_dir}/User/settings.json"))
-    };
+    let settings_path = vscode_user_dir(home, app_dir).join(dest);
     std::fs::create_dir_all(
         settings_path
             .parent()
@@ -185,3 +200,263 @@ pub fn install_alacritty_fixture(home: &Path, fixture_name: &str) {
     .expect("failed to create alacritty config directory");
     std::fs::copy(&fixture_path, &config_path).expect("failed to copy alacritty config fixture");
 }
+
+pub fn install_dconf_fixture(home: &Path, fixture_name: &str) {
+    let fixture_path = Path::new("tests")
+        .join("fixtures")
+        .join("dconf")
+        .join(fixture_name);
+    let db_path = home.join(".config/dconf/user");
+    std::fs::create_dir_all(
+        db_path
+            .parent()
+            .expect("dconf database should have parent directory"),
+    )
+    .expect("failed to create dconf directory");
+    std::fs::copy(&fixture_path, &db_path).expect("failed to copy dconf fixture");
+}
+
+/// Copies a fixture from `fixtures/<terminal>` to `dest`, relative to `home`.
+pub fn install_config_fixture(home: &Path, terminal: &str, fixture_name: &str, dest: &str) {
+    let fixture_path = Path::new("tests")
+        .join("fixtures")
+        .join(terminal)
+        .join(fixture_name);
+    let dest_path = home.join(dest);
+    std::fs::create_dir_all(
+        dest_path
+            .parent()
+            .expect("config file should have parent directory"),
+    )
+    .expect("failed to create config directory");
+    std::fs::copy(&fixture_path, &dest_path).expect("failed to copy config fixture");
+}
+
+/// Directory that desktop apps keep their config in, relative to `home`.
+pub fn app_config_dir() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Library/Application Support"
+    } else {
+        ".config"
+    }
+}
+
+/// Copies a fixture from `fixtures/<terminal>` to `dest`, relative to
+/// [`app_config_dir`].
+pub fn install_app_config_fixture(home: &Path, terminal: &str, fixture_name: &str, dest: &str) {
+    let dest = format!("{}/{dest}", app_config_dir());
+    install_config_fixture(home, terminal, fixture_name, &dest);
+}
+
+/// Installs an xfce4-terminal fixture as the xfconf channel file `channel`.
+pub fn install_xfconf_fixture(home: &Path, fixture_name: &str, channel: &str) {
+    let dest = format!(".config/xfce4/xfconf/xfce-perchannel-xml/{channel}.xml");
+    install_config_fixture(home, "xfce4_terminal", fixture_name, &dest);
+}
+
+/// Installs a Windows Terminal settings fixture for `user` under a fake
+/// Windows system drive at `windows_root`, at `location` relative to the
+/// user profile.
+pub fn install_windows_terminal_fixture(
+    windows_root: &Path,
+    user: &str,
+    fixture_name: &str,
+    location: &str,
+) {
+    let dest = format!("Users/{user}/{location}");
+    install_config_fixture(windows_root, "windows_terminal", fixture_name, &dest);
+}
+
+/// Writes an executable shell script named `name` into `<home>/bin` and
+/// returns that directory, for use as `PATH`.
+pub fn install_stub_command(home: &Path, name: &str, script: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let bin = home.join("bin");
+    std::fs::create_dir_all(&bin).expect("failed to create stub bin directory");
+    let path = bin.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}")).expect("failed to write stub command");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .expect("failed to make stub command executable");
+    bin
+}
+
+/// What a run of the CLI on a pseudo-terminal left behind.
+pub struct PtyRun {
+    pub output: Output,
+    /// Everything the CLI wrote to the terminal.
+    pub written: Vec<u8>,
+    /// Whether the terminal was in raw mode, without echo, when the first
+    /// query arrived.
+    pub raw: bool,
+    /// Whether the terminal's modes were the same after the run as before.
+    pub restored: bool,
+}
+
+/// Runs the CLI with a fresh pseudo-terminal as its controlling terminal,
+/// standing in for a terminal that answers queries: each time the CLI ends a
+/// batch of queries with a DA1 request, the next of `replies` is written back.
+pub fn run_cli_in_pty(args: &[&str], env: &[(&str, &str)], replies: &[&[u8]]) -> PtyRun {
+    use std::io::{Read, Write};
+    use std::os::fd::{FromRawFd, OwnedFd};
+    use std::os::unix::process::CommandExt;
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    let (mut master, slave) = {
+        let (mut master, mut slave) = (-1, -1);
+        let opened = unsafe {
+            libc::openpty(
+                &mut master,
+                &mut slave,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        assert_eq!(opened, 0, "failed to open a pseudo-terminal");
+        unsafe {
+            (
+                std::fs::File::from(OwnedFd::from_raw_fd(master)),
+                OwnedFd::from_raw_fd(slave),
+            )
+        }
+    };
+    let modes_before = terminal_modes(&slave);
+
+    let mut command = Command::new(assert_cmd::cargo::cargo_bin!("has-nerd-font"));
+    command.env_clear();
+    command.args(args);
+    command.envs(env.iter().copied());
+    command.stdin(Stdio::from(
+        slave.try_clone().expect("failed to clone pty slave"),
+    ));
+    command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
+    // Start a new session so that the pty becomes the controlling terminal,
+    // which is what `/dev/tty` opens.
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() < 0 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let mut child = command.spawn().expect("failed to execute has-nerd-font");
+
+    let mut written = Vec::new();
+    let mut raw = false;
+    let mut answered = 0;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while child.try_wait().expect("failed to poll child").is_none() {
+        assert!(Instant::now() < deadline, "has-nerd-font did not exit");
+        let mut poll = libc::pollfd {
+            fd: std::os::fd::AsRawFd::as_raw_fd(&master),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        if unsafe { libc::poll(&mut poll, 1, 20) } <= 0 {
+            continue;
+        }
+        let mut chunk = [0; 256];
+        let n = master.read(&mut chunk).expect("failed to read from pty");
+        if written.is_empty() {
+            let local = terminal_modes(&slave)[3];
+            raw = local & (libc::ICANON | libc::ECHO) == 0;
+        }
+        written.extend_from_slice(&chunk[..n]);
+
+        let requested = written.windows(3).filter(|w| *w == b"\x1b[c").count();
+        for reply in replies.iter().take(requested).skip(answered) {
+            master.write_all(reply).expect("failed to write to pty");
+        }
+        answered = answered.max(requested.min(replies.len()));
+    }
+
+    let output = child
+        .wait_with_output()
+        .expect("failed to collect has-nerd-font output");
+    PtyRun {
+        output,
+        written,
+        raw,
+        restored: terminal_modes(&slave) == modes_before,
+    }
+}
+
+/// The input, output, control and local mode flags of a terminal.
+fn terminal_modes(tty: &std::os::fd::OwnedFd) -> [libc::tcflag_t; 4] {
+    let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+    let result =
+        unsafe { libc::tcgetattr(std::os::fd::AsRawFd::as_raw_fd(tty), termios.as_mut_ptr()) };
+    assert_eq!(result, 0, "failed to read terminal modes");
+    let termios = unsafe { termios.assume_init() };
+    [
+        termios.c_iflag,
+        termios.c_oflag,
+        termios.c_cflag,
+        termios.c_lflag,
+    ]
+}
+
+/// A fake procfs tree for exercising process-tree detection.
+pub struct FakeProc {
+    root: PathBuf,
+}
+
+impl FakeProc {
+    /// Creates an empty tree whose `self` entry points at `self_pid`.
+    pub fn new(home: &Path, self_pid: u32) -> Self {
+        let root = home.join("proc");
+        std::fs::create_dir_all(&root).expect("failed to create fake proc root");
+        std::os::unix::fs::symlink(self_pid.to_string(), root.join("self"))
+            .expect("failed to link fake proc self");
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Adds a process entry; `exe` is written as a (dangling) symlink.
+    pub fn add(&self, pid: u32, ppid: u32, comm: &str, exe: Option<&str>) -> PathBuf {
+        let dir = self.root.join(pid.to_string());
+        std::fs::create_dir_all(&dir).expect("failed to create fake proc entry");
+        std::fs::write(
+            dir.join("stat"),
+            format!("{pid} ({comm}) S {ppid} {pid} {pid} 0 -1 4194304 0 0 0 0\n"),
+        )
+        .expect("failed to write fake stat");
+        std::fs::write(dir.join("comm"), format!("{comm}\n")).expect("failed to write fake comm");
+        self.set_uids(pid, 1000, 1000);
+        if let Some(exe) = exe {
+            std::os::unix::fs::symlink(exe, dir.join("exe")).expect("failed to link fake exe");
+        }
+        dir
+    }
+
+    pub fn set_uids(&self, pid: u32, real: u32, effective: u32) {
+        let status = format!("Name:\tfake\nUid:\t{real}\t{effective}\t{effective}\t{effective}\n");
+        std::fs::write(self.root.join(pid.to_string()).join("status"), status)
+            .expect("failed to write fake status");
+    }
+
+    pub fn set_cmdline(&self, pid: u32, args: &[&str]) {
+        let raw: Vec<u8> = args
+            .iter()
+            .flat_map(|arg| format!("{arg}\0").into_bytes())
+            .collect();
+        std::fs::write(self.root.join(pid.to_string()).join("cmdline"), raw)
+            .expect("failed to write fake cmdline");
+    }
+
+    pub fn set_environ(&self, pid: u32, environ: &[(&str, &str)]) {
+        let raw: Vec<u8> = environ
+            .iter()
+            .flat_map(|(key, value)| format!("{key}={value}\0").into_bytes())
+            .collect();
+        std::fs::write(self.root.join(pid.to_string()).join("environ"), raw)
+            .expect("failed to write fake environ");
+    }
+}
@@ -0,0 +1,105 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+const APP_SUPPORT_NORMALIZATIONS: &[(&str, &str)] = if cfg!(target_os = "macos") {
+    &[("Library/Application Support", "<APP_SUPPORT>")]
+} else {
+    &[(".config", "<APP_SUPPORT>")]
+};
+
+fn run_tabby(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    support::run_cli(
+        &["--json", "--explain"],
+        &[("HOME", &home_str), ("TERM_PROGRAM", "Tabby")],
+        None,
+    )
+}
+
+fn json_snapshot(output: &std::process::Output) -> String {
+    support::stdout_json_snapshot_with_extra_normalizations(output, APP_SUPPORT_NORMALIZATIONS)
+}
+
+#[test]
+fn tabby_nerd_font() {
+    let home = support::scenario_home("tabby-nerd-font");
+    support::install_app_config_fixture(
+        &home,
+        "tabby",
+        "config-nerd-font.yaml",
+        "tabby/config.yaml",
+    );
+
+    let output = run_tabby(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("tabby_nerd_font_json", json_snapshot(&output));
+    assert_snapshot!("tabby_nerd_font_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn tabby_fallback_font() {
+    let home = support::scenario_home("tabby-fallback-font");
+    support::install_app_config_fixture(
+        &home,
+        "tabby",
+        "config-fallback-font.yaml",
+        "tabby/config.yaml",
+    );
+
+    let output = run_tabby(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("tabby_fallback_font_json", json_snapshot(&output));
+}
+
+#[test]
+fn tabby_plain_font() {
+    let home = support::scenario_home("tabby-plain");
+    support::install_app_config_fixture(&home, "tabby", "config-plain.yaml", "tabby/config.yaml");
+
+    let output = run_tabby(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("tabby_plain_json", json_snapshot(&output));
+}
+
+#[test]
+fn tabby_without_font_uses_default() {
+    let home = support::scenario_home("tabby-no-font");
+    support::install_app_config_fixture(&home, "tabby", "config-no-font.yaml", "tabby/config.yaml");
+
+    let output = run_tabby(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    // The default font differs per platform.
+    let json: serde_json::Value =
+        serde_json::from_str(&support::stdout_text(&output)).expect("stdout should be JSON");
+    let default_font = if cfg!(target_os = "macos") {
+        "Menlo"
+    } else {
+        "Liberation Mono"
+    };
+    assert_eq!(json["font"], default_font);
+    assert_eq!(json["confidence"], "probable");
+    assert!(json["config_path"].is_string());
+}
+
+#[test]
+fn tabby_malformed_config() {
+    let home = support::scenario_home("tabby-malformed");
+    support::install_app_config_fixture(
+        &home,
+        "tabby",
+        "config-malformed.yaml",
+        "tabby/config.yaml",
+    );
+
+    let output = run_tabby(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!("tabby_malformed_json", json_snapshot(&output));
+}
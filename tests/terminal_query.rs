@@ -0,0 +1,55 @@
+mod support;
+
+use insta::assert_snapshot;
+
+const XTVERSION: &[u8] = b"\x1b[>0q";
+const DA1_REPLY: &[u8] = b"\x1b[?62;22c";
+
+fn path() -> String {
+    std::env::var("PATH").unwrap_or_default()
+}
+
+#[test]
+fn query_identifies_bundled_terminal() {
+    let path = path();
+    let run = support::run_cli_in_pty(
+        &["--json", "--query-terminal"],
+        &[("PATH", &path)],
+        &[b"\x1bP>|kitty(0.31.0)\x1b\\\x1b[>1;4000;31c\x1b[?62;22c"],
+    );
+
+    assert_eq!(run.output.status.code(), Some(0));
+    assert!(run.written.starts_with(XTVERSION));
+    assert!(run.raw, "queries should be sent in raw mode");
+    assert!(run.restored, "terminal modes should be restored");
+    assert_snapshot!(
+        "terminal_query_kitty_json",
+        support::stdout_json_snapshot(&run.output)
+    );
+}
+
+#[test]
+fn query_answered_by_tmux_is_inconclusive() {
+    let path = path();
+    let run = support::run_cli_in_pty(
+        &["--json", "--query-terminal"],
+        &[("PATH", &path)],
+        &[b"\x1bP>|tmux 3.4\x1b\\\x1b[>84;0;0c\x1b[?62;22c", DA1_REPLY],
+    );
+
+    assert_eq!(run.output.status.code(), Some(2));
+    assert!(run.restored, "terminal modes should be restored");
+    assert_snapshot!(
+        "terminal_query_tmux_json",
+        support::stdout_json_snapshot(&run.output)
+    );
+}
+
+#[test]
+fn silent_terminal_is_left_as_found() {
+    let path = path();
+    let run = support::run_cli_in_pty(&["--json", "--query-terminal"], &[("PATH", &path)], &[]);
+
+    assert_eq!(run.output.status.code(), Some(2));
+    assert!(run.restored, "terminal modes should be restored");
+}
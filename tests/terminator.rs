@@ -0,0 +1,131 @@
+mod support;
+
+use std::path::Path;
+
+use has_nerd_font::{DetectOptions, detect_with};
+use insta::assert_snapshot;
+
+fn run_terminator(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+
+    support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("VTE_VERSION", "7600"),
+            (
+                "TERMINATOR_UUID",
+                "urn:uuid:5e0c8f3a-2b1d-4c6e-9f7a-0d4b3e2a1c98",
+            ),
+        ],
+        None,
+    )
+}
+
+#[test]
+fn terminator_default_profile_nerd_font() {
+    let home = support::scenario_home("terminator-default-profile");
+    support::install_config_fixture(
+        &home,
+        "terminator",
+        "config-profiles",
+        ".config/terminator/config",
+    );
+
+    let output = run_terminator(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "terminator_default_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "terminator_default_profile_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn terminator_profile_from_command_line() {
+    let home = support::scenario_home("terminator-launch-profile");
+    support::install_config_fixture(
+        &home,
+        "terminator",
+        "config-profiles",
+        ".config/terminator/config",
+    );
+    let home_str = home.to_string_lossy().to_string();
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(100, 1, "terminator", Some("/usr/bin/python3.12"));
+    proc.set_cmdline(
+        100,
+        &["/usr/bin/python3", "/usr/bin/terminator", "-p", "work"],
+    );
+    proc.add(200, 100, "bash", Some("/usr/bin/bash"));
+    proc.add(300, 200, "has-nerd-font", None);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let vars = [
+        ("HOME".to_string(), home_str),
+        ("TERMINATOR_UUID".to_string(), "urn:uuid:1".to_string()),
+    ];
+    let result = detect_with(&vars, &options);
+
+    assert_snapshot!(
+        "terminator_launch_profile_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn terminator_system_font() {
+    let home = support::scenario_home("terminator-system-font");
+    support::install_config_fixture(
+        &home,
+        "terminator",
+        "config-system-font",
+        ".config/terminator/config",
+    );
+    support::install_dconf_fixture(&home, "gnome-terminal-system-font.gvdb");
+
+    let output = run_terminator(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "terminator_system_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn terminator_without_config_uses_system_default() {
+    let home = support::scenario_home("terminator-no-config");
+
+    let output = run_terminator(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "terminator_no_config_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn terminator_malformed_config() {
+    let home = support::scenario_home("terminator-malformed");
+    support::install_config_fixture(
+        &home,
+        "terminator",
+        "config-malformed",
+        ".config/terminator/config",
+    );
+
+    let output = run_terminator(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "terminator_malformed_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,101 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+const FONT: &str = ".termux/font.ttf";
+
+fn run_termux(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    support::run_cli(
+        &["--json", "--explain"],
+        &[("HOME", &home_str), ("TERMUX_VERSION", "0.118.1")],
+        None,
+    )
+}
+
+#[test]
+fn termux_nerd_font() {
+    let home = support::scenario_home("termux-nerd-font");
+    support::install_config_fixture(&home, "termux", "nerd-font.ttf", FONT);
+
+    let output = run_termux(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "termux_nerd_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("termux_nerd_font_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn termux_plain_font() {
+    let home = support::scenario_home("termux-plain");
+    support::install_config_fixture(&home, "termux", "plain.ttf", FONT);
+
+    let output = run_termux(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("termux_plain_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn termux_postscript_name_counts() {
+    let home = support::scenario_home("termux-postscript-nf");
+    support::install_config_fixture(&home, "termux", "postscript-nf.ttf", FONT);
+
+    let output = run_termux(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "termux_postscript_nf_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn termux_without_custom_font() {
+    let home = support::scenario_home("termux-default");
+
+    let output = run_termux(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "termux_default_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn termux_detected_from_prefix() {
+    let home = support::scenario_home("termux-prefix");
+    support::install_config_fixture(&home, "termux", "nerd-font.ttf", FONT);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[
+            ("HOME", &home_str),
+            ("PREFIX", "/data/data/com.termux/files/usr"),
+        ],
+        None,
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn termux_invalid_font_file() {
+    let home = support::scenario_home("termux-invalid");
+    support::install_config_fixture(&home, "termux", "not-a-font.ttf", FONT);
+
+    let output = run_termux(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "termux_invalid_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
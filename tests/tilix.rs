@@ -0,0 +1,77 @@
+mod support;
+
+use std::path::Path;
+
+use has_nerd_font::{DetectOptions, detect_with};
+use insta::assert_snapshot;
+
+fn run_tilix(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+
+    support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("VTE_VERSION", "6003"),
+            ("TILIX_ID", "9d2f6a1e-7c3b-4e8a-b5d0-1f4e6c2a8b37"),
+        ],
+        None,
+    )
+}
+
+#[test]
+fn tilix_default_profile_nerd_font() {
+    let home = support::scenario_home("tilix-default-profile");
+    support::install_dconf_fixture(&home, "tilix-profiles.gvdb");
+
+    let output = run_tilix(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "tilix_default_profile_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "tilix_default_profile_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn tilix_profile_from_command_line() {
+    let home = support::scenario_home("tilix-launch-profile");
+    support::install_dconf_fixture(&home, "tilix-profiles.gvdb");
+    let home_str = home.to_string_lossy().to_string();
+
+    let proc = support::FakeProc::new(&home, 300);
+    proc.add(100, 1, "tilix", Some("/usr/bin/tilix"));
+    proc.set_cmdline(100, &["tilix", "--profile=Work"]);
+    proc.add(200, 100, "bash", Some("/usr/bin/bash"));
+    proc.add(300, 200, "has-nerd-font", None);
+
+    let options = DetectOptions::default().with_proc_root(proc.root());
+    let vars = [
+        ("HOME".to_string(), home_str),
+        ("VTE_VERSION".to_string(), "6003".to_string()),
+    ];
+    let result = detect_with(&vars, &options);
+
+    assert_snapshot!(
+        "tilix_launch_profile_json",
+        support::result_json_snapshot(&result)
+    );
+}
+
+#[test]
+fn tilix_follows_system_font() {
+    let home = support::scenario_home("tilix-system-font");
+    support::install_dconf_fixture(&home, "gnome-terminal-system-font.gvdb");
+
+    let output = run_tilix(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "tilix_system_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
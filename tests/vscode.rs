@@ -11,7 +11,20 @@ const APP_SUPPORT_NORMALIZATIONS: &[(&str, &str)] = if cfg!(target_os = "macos")
     &[(".config", "<APP_SUPPORT>")]
 };
 
-fn vscode_env<'a>(home_str: &'a str) -> Vec<(&'a str, &'a str)> {
+/// Also hides which platform's default font was reported.
+const DEFAULT_FONT_NORMALIZATIONS: &[(&str, &str)] = if cfg!(target_os = "macos") {
+    &[
+        ("Library/Application Support", "<APP_SUPPORT>"),
+        ("Menlo", "<DEFAULT_FONT>"),
+    ]
+} else {
+    &[
+        (".config", "<APP_SUPPORT>"),
+        ("Droid Sans Mono", "<DEFAULT_FONT>"),
+    ]
+};
+
+fn vscode_env(home_str: &str) -> Vec<(&str, &str)> {
     vec![
         ("TERM_PROGRAM", "vscode"),
         ("HOME", home_str),
@@ -107,10 +120,79 @@ fn vscode_malformed_snapshots_json_and_explain() {
     );
 }
 
+#[test]
+fn vscode_empty_terminal_font_falls_back_to_editor_font() {
+    let home = support::scenario_home("vscode-empty-terminal-font");
+    support::install_vscode_fixture(&home, "vscode-empty-terminal-font.jsonc", VSCODE_APP_DIR);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), None);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "vscode_empty_terminal_font_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_reports_matching_family_of_font_list() {
+    let home = support::scenario_home("vscode-symbols-fallback");
+    support::install_vscode_fixture(&home, "vscode-symbols-fallback.jsonc", VSCODE_APP_DIR);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), None);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "vscode_symbols_fallback_json",
+        project_json_snapshot(&output)
+    );
+}
+
+/// Asserts that `output` reports VS Code's default font for this platform.
+fn assert_default_font(output: &std::process::Output) {
+    assert_eq!(output.status.code(), Some(6));
+    let json: serde_json::Value =
+        serde_json::from_str(&support::stdout_text(output)).expect("stdout should be JSON");
+    let default_font = if cfg!(target_os = "macos") {
+        "Menlo"
+    } else {
+        "Droid Sans Mono"
+    };
+    assert_eq!(json["font"], default_font);
+    assert_eq!(json["detected"], false);
+    assert_eq!(json["confidence"], "probable");
+}
+
+#[test]
+fn vscode_without_font_uses_default() {
+    let home = support::scenario_home("vscode-no-font");
+    support::install_vscode_fixture(&home, "vscode-no-font.jsonc", VSCODE_APP_DIR);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), None);
+
+    assert_default_font(&output);
+    let json: serde_json::Value =
+        serde_json::from_str(&support::stdout_text(&output)).expect("stdout should be JSON");
+    assert!(json["config_path"].is_string());
+}
+
+#[test]
+fn vscode_without_settings_uses_default() {
+    let home = support::scenario_home("vscode-no-settings");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), None);
+
+    assert_default_font(&output);
+}
+
 const VSCODIUM_ASKPASS: &str = "/app/share/codium/codium";
 const VSCODIUM_APP_DIR: &str = "VSCodium";
 
-fn vscodium_env<'a>(home_str: &'a str) -> Vec<(&'a str, &'a str)> {
+fn vscodium_env(home_str: &str) -> Vec<(&str, &str)> {
     vec![
         ("TERM_PROGRAM", "vscode"),
         ("HOME", home_str),
@@ -139,3 +221,588 @@ fn vscodium_nerd_font_editor_snapshots_json_and_explain() {
         support::stderr_text_normalized(&output, APP_SUPPORT_NORMALIZATIONS)
     );
 }
+
+/// Runs the CLI as a VS Code terminal with `extra` env vars, returning the
+/// JSON snapshot and the exit code.
+fn run_fork(home: &std::path::Path, extra: &[(&str, &str)]) -> (String, Option<i32>) {
+    let home_str = home.to_string_lossy().to_string();
+    let mut env = vec![("TERM_PROGRAM", "vscode"), ("HOME", home_str.as_str())];
+    env.extend_from_slice(extra);
+    let output = support::run_cli(&["--json"], &env, None);
+    (project_json_snapshot(&output), output.status.code())
+}
+
+#[test]
+fn cursor_identified_by_trace_id() {
+    let home = support::scenario_home("vscode-fork-cursor");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", "Cursor");
+
+    // Cursor's askpass is a plain Electron binary in some packagings.
+    let (json, code) = run_fork(
+        &home,
+        &[
+            ("CURSOR_TRACE_ID", "9f3c1e2a7b"),
+            ("VSCODE_GIT_ASKPASS_NODE", "/usr/lib/electron/electron"),
+        ],
+    );
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_cursor_json", json);
+}
+
+#[test]
+fn insiders_askpass_is_not_mistaken_for_code() {
+    let home = support::scenario_home("vscode-fork-insiders");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", "Code - Insiders");
+
+    let (json, code) = run_fork(
+        &home,
+        &[(
+            "VSCODE_GIT_ASKPASS_NODE",
+            "/usr/share/code-insiders/code-insiders",
+        )],
+    );
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_insiders_json", json);
+}
+
+#[test]
+fn insiders_identified_by_version_suffix() {
+    let home = support::scenario_home("vscode-fork-insiders-version");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", "Code - Insiders");
+
+    let (json, code) = run_fork(
+        &home,
+        &[
+            ("TERM_PROGRAM_VERSION", "1.96.0-insider"),
+            ("VSCODE_GIT_ASKPASS_NODE", "/usr/lib/electron/electron"),
+        ],
+    );
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_insiders_version_json", json);
+}
+
+#[test]
+fn windsurf_identified_by_bundle_id() {
+    let home = support::scenario_home("vscode-fork-windsurf");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", "Windsurf");
+
+    let (json, code) = run_fork(
+        &home,
+        &[
+            ("__CFBundleIdentifier", "com.exafunction.windsurf"),
+            ("VSCODE_GIT_ASKPASS_NODE", "/usr/lib/electron/electron"),
+        ],
+    );
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_windsurf_json", json);
+}
+
+#[test]
+fn deepest_askpass_component_decides_fork() {
+    let home = support::scenario_home("vscode-fork-cursor-deepest");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", "Cursor");
+
+    // A user named "code" doesn't make this VS Code.
+    let (json, code) = run_fork(
+        &home,
+        &[(
+            "VSCODE_GIT_ASKPASS_NODE",
+            "/home/code/Applications/cursor/cursor",
+        )],
+    );
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_cursor_deepest_json", json);
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn flatpak_settings_preferred_inside_flatpak() {
+    let home = support::scenario_home("vscode-fork-flatpak");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "vscode-nerd-font-terminal.jsonc",
+        ".var/app/com.visualstudio.code/config/Code/User/settings.json",
+    );
+
+    let (json, code) = run_fork(
+        &home,
+        &[
+            ("VSCODE_GIT_ASKPASS_NODE", "/app/extra/vscode/code"),
+            (
+                "VSCODE_IPC_HOOK_CLI",
+                "/run/user/1000/app/com.visualstudio.code/vscode-ipc-3b9e8c1d.sock",
+            ),
+        ],
+    );
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_flatpak_json", json);
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn snap_settings_used_without_native_install() {
+    let home = support::scenario_home("vscode-fork-snap");
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "vscode-nerd-font-terminal.jsonc",
+        "snap/codium/current/.config/VSCodium/User/settings.json",
+    );
+
+    let (json, code) = run_fork(&home, &[("VSCODE_GIT_ASKPASS_NODE", VSCODIUM_ASKPASS)]);
+
+    assert_eq!(code, Some(0));
+    assert_snapshot!("vscode_fork_snap_json", json);
+}
+
+#[test]
+fn unrecognized_fork() {
+    let home = support::scenario_home("vscode-fork-unrecognized");
+
+    let (json, code) = run_fork(
+        &home,
+        &[("VSCODE_GIT_ASKPASS_NODE", "/usr/lib/electron/electron")],
+    );
+
+    assert_eq!(code, Some(5));
+    assert_snapshot!("vscode_fork_unrecognized_json", json);
+}
+
+/// Creates `projects/app/src` under `home` and returns it, to run from.
+fn project_cwd(home: &std::path::Path) -> std::path::PathBuf {
+    let cwd = home.join("projects/app/src");
+    std::fs::create_dir_all(&cwd).expect("failed to create project directory");
+    cwd
+}
+
+fn project_json_snapshot(output: &std::process::Output) -> String {
+    support::stdout_json_snapshot_with_extra_normalizations(output, APP_SUPPORT_NORMALIZATIONS)
+}
+
+#[test]
+fn vscode_folder_settings_override_user_settings() {
+    let home = support::scenario_home("vscode-folder-settings");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project-terminal-nerd.jsonc",
+        "projects/app/.vscode/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "vscode_folder_settings_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_workspace_file_settings_override_user_settings() {
+    let home = support::scenario_home("vscode-workspace-file");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project.code-workspace",
+        "projects/project.code-workspace",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("vscode_workspace_file_json", project_json_snapshot(&output));
+}
+
+#[test]
+fn vscode_folder_settings_override_workspace_file() {
+    let home = support::scenario_home("vscode-folder-over-workspace");
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project.code-workspace",
+        "projects/project.code-workspace",
+    );
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project-terminal-nerd.jsonc",
+        "projects/app/.vscode/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "vscode_folder_over_workspace_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_folder_outside_workspace_is_ignored() {
+    let home = support::scenario_home("vscode-folder-outside-workspace");
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project.code-workspace",
+        "projects/project.code-workspace",
+    );
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project-terminal-nerd.jsonc",
+        "projects/scratch/.vscode/settings.json",
+    );
+    let cwd = home.join("projects/scratch");
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "vscode_folder_outside_workspace_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_workspace_file_above_home_is_ignored() {
+    let scenario = support::scenario_home("vscode-workspace-above-home");
+    support::install_config_fixture(
+        &scenario,
+        "vscode",
+        "project.code-workspace",
+        "stray.code-workspace",
+    );
+    let home = scenario.join("me");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "vscode_workspace_above_home_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_workspace_file_above_symlinked_home_is_ignored() {
+    let scenario = support::scenario_home("vscode-workspace-above-symlinked-home");
+    support::install_config_fixture(
+        &scenario,
+        "vscode",
+        "project.code-workspace",
+        "real/stray.code-workspace",
+    );
+    std::fs::create_dir_all(scenario.join("real/me")).expect("failed to create home");
+    std::os::unix::fs::symlink("real", scenario.join("link")).expect("failed to link home");
+    let home = scenario.join("link/me");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "vscode_workspace_above_symlinked_home_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_empty_folder_terminal_font_overrides_user_terminal_font() {
+    let home = support::scenario_home("vscode-empty-folder-terminal-font");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", VSCODE_APP_DIR);
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project-terminal-empty.jsonc",
+        "projects/app/.vscode/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "vscode_empty_folder_terminal_font_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_user_terminal_font_beats_project_editor_font() {
+    let home = support::scenario_home("vscode-user-terminal-font");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", VSCODE_APP_DIR);
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project-editor-plain.jsonc",
+        "projects/app/.vscode/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "vscode_user_terminal_font_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_malformed_folder_settings() {
+    let home = support::scenario_home("vscode-malformed-folder");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", VSCODE_APP_DIR);
+    support::install_config_fixture(
+        &home,
+        "vscode",
+        "project-malformed.jsonc",
+        "projects/app/.vscode/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "vscode_malformed_folder_json",
+        project_json_snapshot(&output)
+    );
+}
+
+const PRESENTATION_PROFILE: &str = "-6b0ad0bd";
+
+/// Writes `User/globalStorage/storage.json` with a "Presentation" profile,
+/// associated with `workspace` if given.
+fn write_profile_storage(home: &std::path::Path, workspace: Option<&std::path::Path>, extra: &str) {
+    let associations = workspace.map_or(String::new(), |workspace| {
+        format!(
+            r#""file://{}": "{PRESENTATION_PROFILE}","#,
+            workspace.display()
+        )
+    });
+    let storage = format!(
+        r#"{{
+  "userDataProfiles": [
+    {{ "location": "{PRESENTATION_PROFILE}", "name": "Presentation"{extra} }}
+  ],
+  "profileAssociations": {{
+    "workspaces": {{
+      {associations}
+      "file:///elsewhere": "__default__profile__"
+    }},
+    "emptyWindows": {{}}
+  }},
+  "windowControlHeight": 35
+}}"#
+    );
+    let path = support::vscode_user_dir(home, VSCODE_APP_DIR).join("globalStorage/storage.json");
+    std::fs::create_dir_all(
+        path.parent()
+            .expect("storage.json should have parent directory"),
+    )
+    .expect("failed to create globalStorage directory");
+    std::fs::write(path, storage).expect("failed to write storage.json");
+}
+
+fn install_presentation_profile(home: &std::path::Path, fixture_name: &str) {
+    support::install_vscode_user_fixture(
+        home,
+        fixture_name,
+        VSCODE_APP_DIR,
+        &format!("profiles/{PRESENTATION_PROFILE}/settings.json"),
+    );
+}
+
+#[test]
+fn vscode_workspace_profile_settings() {
+    let home = support::scenario_home("vscode-profile");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    install_presentation_profile(&home, "vscode-nerd-font-terminal.jsonc");
+    write_profile_storage(&home, Some(&home.join("projects/app")), "");
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("vscode_profile_json", project_json_snapshot(&output));
+}
+
+#[test]
+fn vscode_profile_does_not_inherit_default_profile_settings() {
+    let home = support::scenario_home("vscode-profile-no-font");
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", VSCODE_APP_DIR);
+    install_presentation_profile(&home, "profile-no-font.jsonc");
+    write_profile_storage(&home, Some(&home.join("projects/app")), "");
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "vscode_profile_no_font_json",
+        support::stdout_json_snapshot_with_extra_normalizations(
+            &output,
+            DEFAULT_FONT_NORMALIZATIONS
+        )
+    );
+}
+
+#[test]
+fn vscode_unassociated_workspace_uses_default_profile() {
+    let home = support::scenario_home("vscode-profile-unassociated");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    install_presentation_profile(&home, "vscode-nerd-font-terminal.jsonc");
+    write_profile_storage(&home, None, "");
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "vscode_profile_unassociated_json",
+        project_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_profile_sharing_default_settings() {
+    let home = support::scenario_home("vscode-profile-shared-settings");
+    support::install_vscode_fixture(&home, "vscode-default.jsonc", VSCODE_APP_DIR);
+    install_presentation_profile(&home, "vscode-nerd-font-terminal.jsonc");
+    write_profile_storage(
+        &home,
+        Some(&home.join("projects/app")),
+        r#", "useDefaultFlags": { "settings": true }"#,
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(&["--json"], &vscode_env(&home_str), Some(&cwd));
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "vscode_profile_shared_settings_json",
+        project_json_snapshot(&output)
+    );
+}
+
+const SERVER_ASKPASS: &str =
+    "/home/me/.vscode-server/bin/f1e16e1e6214d7c44d078b1f0607b2388f29d729/node";
+
+/// Runs the CLI in a VS Code remote terminal whose local settings, if read,
+/// would say yes.
+fn run_remote(scenario: &str, extra: &[(&str, &str)]) -> std::process::Output {
+    let home = support::scenario_home(scenario);
+    support::install_vscode_fixture(&home, "vscode-nerd-font-terminal.jsonc", VSCODE_APP_DIR);
+    let home_str = home.to_string_lossy().to_string();
+    let mut env = vec![("TERM_PROGRAM", "vscode"), ("HOME", home_str.as_str())];
+    env.extend_from_slice(extra);
+    support::run_cli(&["--json", "--explain"], &env, None)
+}
+
+#[test]
+fn vscode_dev_container_is_remote_session() {
+    let output = run_remote(
+        "vscode-remote-dev-container",
+        &[
+            ("REMOTE_CONTAINERS", "true"),
+            ("VSCODE_GIT_ASKPASS_NODE", SERVER_ASKPASS),
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_snapshot!(
+        "vscode_remote_dev_container_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "vscode_remote_dev_container_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn vscode_codespace_is_remote_session() {
+    let output = run_remote(
+        "vscode-remote-codespaces",
+        &[
+            ("CODESPACES", "true"),
+            (
+                "VSCODE_GIT_ASKPASS_NODE",
+                "/vscode/bin/linux-x64/f1e16e1e6214d7c44d078b1f0607b2388f29d729/node",
+            ),
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_snapshot!(
+        "vscode_remote_codespaces_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_remote_ssh_is_remote_session() {
+    let output = run_remote(
+        "vscode-remote-ssh",
+        &[
+            ("SSH_CONNECTION", "10.0.0.2 51234 10.0.0.1 22"),
+            ("VSCODE_GIT_ASKPASS_NODE", SERVER_ASKPASS),
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_snapshot!(
+        "vscode_remote_ssh_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn vscode_server_askpass_is_remote_session() {
+    let output = run_remote(
+        "vscode-remote-server",
+        &[(
+            "VSCODE_GIT_ASKPASS_NODE",
+            "/home/me/.cursor-server/bin/1649e229afdef8fd1d18ea173f063563f1e722ef/node",
+        )],
+    );
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_snapshot!(
+        "vscode_remote_server_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,128 @@
+#![cfg(not(target_os = "macos"))]
+
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+fn run_warp(home: &Path, version: &str) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("TERM_PROGRAM", "WarpTerminal"),
+            ("TERM_PROGRAM_VERSION", version),
+        ],
+        None,
+    )
+}
+
+const STABLE: &str = "v0.2024.10.29.08.02.stable_02";
+
+#[test]
+fn warp_nerd_font() {
+    let home = support::scenario_home("warp-nerd-font");
+    support::install_config_fixture(
+        &home,
+        "warp",
+        "user_preferences-nerd-font.json",
+        ".config/warp-terminal/user_preferences.json",
+    );
+
+    let output = run_warp(&home, STABLE);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "warp_nerd_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("warp_nerd_font_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn warp_unquoted_font_name() {
+    let home = support::scenario_home("warp-plain");
+    support::install_config_fixture(
+        &home,
+        "warp",
+        "user_preferences-plain.json",
+        ".config/warp-terminal/user_preferences.json",
+    );
+
+    let output = run_warp(&home, STABLE);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("warp_plain_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn warp_preview_reads_its_own_preferences() {
+    let home = support::scenario_home("warp-preview");
+    support::install_config_fixture(
+        &home,
+        "warp",
+        "user_preferences-plain.json",
+        ".config/warp-terminal/user_preferences.json",
+    );
+    support::install_config_fixture(
+        &home,
+        "warp",
+        "user_preferences-nerd-font.json",
+        ".config/warp-terminal-preview/user_preferences.json",
+    );
+
+    let output = run_warp(&home, "v0.2024.11.05.08.02.preview_00");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("warp_preview_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn warp_without_font_uses_default() {
+    let home = support::scenario_home("warp-no-font");
+    support::install_config_fixture(
+        &home,
+        "warp",
+        "user_preferences-no-font.json",
+        ".config/warp-terminal/user_preferences.json",
+    );
+
+    let output = run_warp(&home, STABLE);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("warp_no_font_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn warp_without_preferences_uses_default() {
+    let home = support::scenario_home("warp-no-preferences");
+
+    let output = run_warp(&home, STABLE);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "warp_no_preferences_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn warp_malformed_preferences() {
+    let home = support::scenario_home("warp-malformed");
+    support::install_config_fixture(
+        &home,
+        "warp",
+        "user_preferences-malformed.json",
+        ".config/warp-terminal/user_preferences.json",
+    );
+
+    let output = run_warp(&home, STABLE);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "warp_malformed_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,98 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+fn run_wave(home: &Path, extra: &[(&str, &str)]) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    let mut env = vec![("HOME", home_str.as_str()), ("TERM_PROGRAM", "waveterm")];
+    env.extend_from_slice(extra);
+
+    support::run_cli(&["--json", "--explain"], &env, None)
+}
+
+#[test]
+fn wave_font_family_list() {
+    let home = support::scenario_home("wave-nerd-font");
+    support::install_config_fixture(
+        &home,
+        "wave",
+        "settings-nerd-font.json",
+        ".config/waveterm/settings.json",
+    );
+
+    let output = run_wave(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "wave_nerd_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("wave_nerd_font_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn wave_plain_font() {
+    let home = support::scenario_home("wave-plain");
+    support::install_config_fixture(
+        &home,
+        "wave",
+        "settings-plain.json",
+        ".config/waveterm/settings.json",
+    );
+
+    let output = run_wave(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("wave_plain_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn wave_bundled_hack_font() {
+    let home = support::scenario_home("wave-bundled");
+    support::install_config_fixture(
+        &home,
+        "wave",
+        "settings-bundled.json",
+        ".config/waveterm/settings.json",
+    );
+
+    let output = run_wave(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("wave_bundled_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn wave_without_settings_uses_bundled_font() {
+    let home = support::scenario_home("wave-no-settings");
+
+    let output = run_wave(&home, &[]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "wave_no_settings_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn wave_config_home_override() {
+    let home = support::scenario_home("wave-config-home");
+    support::install_config_fixture(
+        &home,
+        "wave",
+        "settings-plain.json",
+        "wave-config/settings.json",
+    );
+    let config_home = home.join("wave-config").to_string_lossy().to_string();
+
+    let output = run_wave(&home, &[("WAVETERM_CONFIG_HOME", &config_home)]);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "wave_config_home_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
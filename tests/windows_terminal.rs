@@ -0,0 +1,155 @@
+mod support;
+
+use std::path::Path;
+
+use has_nerd_font::{DetectOptions, detect_with};
+use insta::assert_snapshot;
+
+const PACKAGED: &str =
+    "AppData/Local/Packages/Microsoft.WindowsTerminal_8wekyb3d8bbwe/LocalState/settings.json";
+const PREVIEW: &str = "AppData/Local/Packages/Microsoft.WindowsTerminalPreview_8wekyb3d8bbwe/LocalState/settings.json";
+const UNPACKAGED: &str = "AppData/Local/Microsoft/Windows Terminal/settings.json";
+
+const UBUNTU: &str = "{2c4de342-38b7-51cf-b940-2309a097f518}";
+
+fn wsl_vars(extra: &[(&str, &str)]) -> Vec<(String, String)> {
+    [
+        ("WSL_DISTRO_NAME", "Ubuntu"),
+        ("WT_SESSION", "7c6ac1fb-ae0b-4b2d-a1e0-8d2b3e6f9c10"),
+        ("USER", "me"),
+        ("HOME", "/home/me"),
+    ]
+    .iter()
+    .chain(extra)
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+}
+
+fn detect_in(windows_root: &Path, vars: &[(String, String)]) -> String {
+    let options = DetectOptions::default().with_windows_root(windows_root);
+    support::result_json_snapshot(&detect_with(vars, &options))
+}
+
+#[test]
+fn windows_terminal_profile_font() {
+    let root = support::scenario_home("windows-terminal-profile-font");
+    support::install_windows_terminal_fixture(&root, "me", "wt-profile-font.json", PACKAGED);
+
+    assert_snapshot!(
+        "windows_terminal_profile_font_json",
+        detect_in(&root, &wsl_vars(&[("WT_PROFILE_ID", UBUNTU)]))
+    );
+}
+
+#[test]
+fn windows_terminal_default_profile_falls_back_to_defaults() {
+    let root = support::scenario_home("windows-terminal-defaults-font");
+    support::install_windows_terminal_fixture(&root, "me", "wt-defaults-font.json", PREVIEW);
+
+    assert_snapshot!(
+        "windows_terminal_defaults_font_json",
+        detect_in(&root, &wsl_vars(&[]))
+    );
+}
+
+#[test]
+fn windows_terminal_profile_without_font_uses_builtin_default() {
+    let root = support::scenario_home("windows-terminal-default");
+    support::install_windows_terminal_fixture(&root, "me", "wt-default.json", UNPACKAGED);
+
+    assert_snapshot!(
+        "windows_terminal_default_json",
+        detect_in(&root, &wsl_vars(&[("WT_PROFILE_ID", UBUNTU)]))
+    );
+}
+
+#[test]
+fn windows_terminal_legacy_profile_list() {
+    let root = support::scenario_home("windows-terminal-legacy");
+    support::install_windows_terminal_fixture(&root, "me", "wt-legacy.json", PACKAGED);
+
+    assert_snapshot!(
+        "windows_terminal_legacy_json",
+        detect_in(&root, &wsl_vars(&[]))
+    );
+}
+
+#[test]
+fn windows_terminal_malformed_settings() {
+    let root = support::scenario_home("windows-terminal-malformed");
+    support::install_windows_terminal_fixture(&root, "me", "wt-malformed.json", PACKAGED);
+
+    assert_snapshot!(
+        "windows_terminal_malformed_json",
+        detect_in(&root, &wsl_vars(&[]))
+    );
+}
+
+#[test]
+fn windows_terminal_picks_user_matching_linux_user() {
+    let root = support::scenario_home("windows-terminal-several-users");
+    support::install_windows_terminal_fixture(&root, "me", "wt-profile-font.json", PACKAGED);
+    support::install_windows_terminal_fixture(&root, "admin", "wt-default.json", PACKAGED);
+    std::fs::create_dir_all(root.join("Users/Public/AppData")).expect("failed to create Public");
+
+    assert_snapshot!(
+        "windows_terminal_several_users_json",
+        detect_in(&root, &wsl_vars(&[("WT_PROFILE_ID", UBUNTU)]))
+    );
+}
+
+#[test]
+fn windows_terminal_user_from_userprofile() {
+    let root = support::scenario_home("windows-terminal-userprofile");
+    support::install_windows_terminal_fixture(&root, "Me Two", "wt-profile-font.json", PACKAGED);
+    support::install_windows_terminal_fixture(&root, "admin", "wt-default.json", PACKAGED);
+
+    assert_snapshot!(
+        "windows_terminal_userprofile_json",
+        detect_in(
+            &root,
+            &wsl_vars(&[
+                ("USERPROFILE", "C:\\Users\\Me Two"),
+                ("WT_PROFILE_ID", UBUNTU)
+            ])
+        )
+    );
+}
+
+#[test]
+fn windows_terminal_without_settings() {
+    let root = support::scenario_home("windows-terminal-no-settings");
+    std::fs::create_dir_all(root.join("Users/me/AppData")).expect("failed to create profile");
+
+    assert_snapshot!(
+        "windows_terminal_no_settings_json",
+        detect_in(&root, &wsl_vars(&[]))
+    );
+}
+
+#[test]
+fn vscode_in_wsl_reads_windows_side_settings() {
+    let root = support::scenario_home("windows-terminal-vscode-wsl");
+    let settings = root.join("Users/me/AppData/Roaming/Code/User/settings.json");
+    std::fs::create_dir_all(settings.parent().expect("settings should have parent"))
+        .expect("failed to create vscode settings directory");
+    std::fs::copy(
+        "tests/fixtures/vscode/vscode-nerd-font-terminal.jsonc",
+        &settings,
+    )
+    .expect("failed to copy vscode settings fixture");
+
+    assert_snapshot!(
+        "windows_terminal_vscode_wsl_json",
+        detect_in(
+            &root,
+            &wsl_vars(&[
+                ("TERM_PROGRAM", "vscode"),
+                (
+                    "VSCODE_GIT_ASKPASS_NODE",
+                    "/home/me/.vscode-server/bin/f1e16e1e6214d7c44d078b1f0607b2388f29d729/node"
+                ),
+            ])
+        )
+    );
+}
@@ -0,0 +1,107 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+fn run_xfce4_terminal(home: &Path) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+
+    support::run_cli(
+        &["--json", "--explain"],
+        &[
+            ("HOME", &home_str),
+            ("COLORTERM", "xfce4-terminal"),
+            ("VTE_VERSION", "6800"),
+        ],
+        None,
+    )
+}
+
+#[test]
+fn xfce4_terminal_nerd_font() {
+    let home = support::scenario_home("xfce4-terminal-nerd-font");
+    support::install_xfconf_fixture(&home, "xfce4-terminal-nerd-font.xml", "xfce4-terminal");
+
+    let output = run_xfce4_terminal(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "xfce4_terminal_nerd_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!(
+        "xfce4_terminal_nerd_font_explain",
+        support::stderr_text(&output)
+    );
+}
+
+#[test]
+fn xfce4_terminal_system_font() {
+    let home = support::scenario_home("xfce4-terminal-system-font");
+    support::install_xfconf_fixture(&home, "xfce4-terminal-system-font.xml", "xfce4-terminal");
+    support::install_xfconf_fixture(&home, "xsettings.xml", "xsettings");
+
+    let output = run_xfce4_terminal(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "xfce4_terminal_system_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn xfce4_terminal_system_font_without_xsettings() {
+    let home = support::scenario_home("xfce4-terminal-system-font-default");
+    support::install_xfconf_fixture(&home, "xfce4-terminal-system-font.xml", "xfce4-terminal");
+
+    let output = run_xfce4_terminal(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "xfce4_terminal_system_font_default_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn xfce4_terminal_missing_font_key_uses_default() {
+    let home = support::scenario_home("xfce4-terminal-defaults");
+    support::install_xfconf_fixture(&home, "xfce4-terminal-defaults.xml", "xfce4-terminal");
+
+    let output = run_xfce4_terminal(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "xfce4_terminal_defaults_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn xfce4_terminal_without_settings_uses_default() {
+    let home = support::scenario_home("xfce4-terminal-no-settings");
+
+    let output = run_xfce4_terminal(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "xfce4_terminal_no_settings_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn xfce4_terminal_malformed_settings() {
+    let home = support::scenario_home("xfce4-terminal-malformed");
+    support::install_xfconf_fixture(&home, "xfce4-terminal-malformed.xml", "xfce4-terminal");
+
+    let output = run_xfce4_terminal(&home);
+
+    assert_eq!(output.status.code(), Some(5));
+    assert_snapshot!(
+        "xfce4_terminal_malformed_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
@@ -0,0 +1,104 @@
+mod support;
+
+use std::path::Path;
+
+use insta::assert_snapshot;
+
+fn run_with_env(home: &Path, env: &[(&str, &str)]) -> std::process::Output {
+    let home_str = home.to_string_lossy().to_string();
+    let mut vars = vec![("HOME", home_str.as_str())];
+    vars.extend_from_slice(env);
+
+    support::run_cli(&["--json", "--explain"], &vars, None)
+}
+
+fn run_xterm(home: &Path) -> std::process::Output {
+    run_with_env(home, &[("XTERM_VERSION", "XTerm(390)")])
+}
+
+fn run_urxvt(home: &Path) -> std::process::Output {
+    run_with_env(
+        home,
+        &[("TERM", "rxvt-unicode-256color"), ("COLORTERM", "rxvt-xpm")],
+    )
+}
+
+#[test]
+fn xterm_face_name_nerd_font() {
+    let home = support::scenario_home("xterm-face-name");
+    support::install_config_fixture(&home, "xresources", "xterm-face-name", ".Xresources");
+
+    let output = run_xterm(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "xterm_face_name_json",
+        support::stdout_json_snapshot(&output)
+    );
+    assert_snapshot!("xterm_face_name_explain", support::stderr_text(&output));
+}
+
+#[test]
+fn xterm_core_font_from_xdefaults() {
+    let home = support::scenario_home("xterm-core-font");
+    support::install_config_fixture(&home, "xresources", "xterm-core-font", ".Xdefaults");
+
+    let output = run_xterm(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "xterm_core_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn xterm_without_resources_uses_default() {
+    let home = support::scenario_home("xterm-no-resources");
+
+    let output = run_xterm(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "xterm_no_resources_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn urxvt_nerd_font_in_fallback_list() {
+    let home = support::scenario_home("urxvt-fallback");
+    support::install_config_fixture(&home, "xresources", "urxvt-fallback", ".Xresources");
+
+    let output = run_urxvt(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "urxvt_fallback_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn urxvt_class_beats_rxvt_class() {
+    let home = support::scenario_home("urxvt-plain");
+    support::install_config_fixture(&home, "xresources", "urxvt-plain", ".Xresources");
+
+    let output = run_urxvt(&home);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!("urxvt_plain_json", support::stdout_json_snapshot(&output));
+}
+
+#[test]
+fn urxvt_font_from_include() {
+    let home = support::scenario_home("urxvt-include");
+    support::install_config_fixture(&home, "xresources", "include-main", ".Xresources");
+    support::install_config_fixture(&home, "xresources", "include-fonts", ".Xresources.d/fonts");
+    support::install_config_fixture(&home, "xresources", "urxvt-plain", ".Xdefaults");
+
+    let output = run_urxvt(&home);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!("urxvt_include_json", support::stdout_json_snapshot(&output));
+}
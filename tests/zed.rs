@@ -84,3 +84,93 @@ fn zed_malformed_snapshots_json_and_explain() {
         support::stderr_text_normalized(&output, &[])
     );
 }
+
+/// Creates `projects/app/src` under `home` and returns it, to run from.
+fn project_cwd(home: &std::path::Path) -> std::path::PathBuf {
+    let cwd = home.join("projects/app/src");
+    std::fs::create_dir_all(&cwd).expect("failed to create project directory");
+    cwd
+}
+
+#[test]
+fn zed_project_settings_override_user_settings() {
+    let home = support::scenario_home("zed-project-settings");
+    support::install_zed_fixture(&home, "zed-default.jsonc");
+    support::install_config_fixture(
+        &home,
+        "zed",
+        "project-terminal-nerd.jsonc",
+        "projects/app/.zed/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[("TERM_PROGRAM", "zed"), ("HOME", &home_str)],
+        Some(&cwd),
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "zed_project_settings_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn zed_nested_project_settings_override_parent() {
+    let home = support::scenario_home("zed-nested-project");
+    support::install_config_fixture(
+        &home,
+        "zed",
+        "project-terminal-nerd.jsonc",
+        "projects/.zed/settings.json",
+    );
+    support::install_config_fixture(
+        &home,
+        "zed",
+        "project-terminal-plain.jsonc",
+        "projects/app/.zed/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[("TERM_PROGRAM", "zed"), ("HOME", &home_str)],
+        Some(&cwd),
+    );
+
+    assert_eq!(output.status.code(), Some(6));
+    assert_snapshot!(
+        "zed_nested_project_json",
+        support::stdout_json_snapshot(&output)
+    );
+}
+
+#[test]
+fn zed_user_terminal_font_beats_project_buffer_font() {
+    let home = support::scenario_home("zed-user-terminal-font");
+    support::install_zed_fixture(&home, "zed-nerd-font-terminal.jsonc");
+    support::install_config_fixture(
+        &home,
+        "zed",
+        "project-buffer-plain.jsonc",
+        "projects/app/.zed/settings.json",
+    );
+    let cwd = project_cwd(&home);
+    let home_str = home.to_string_lossy().to_string();
+
+    let output = support::run_cli(
+        &["--json"],
+        &[("TERM_PROGRAM", "zed"), ("HOME", &home_str)],
+        Some(&cwd),
+    );
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_snapshot!(
+        "zed_user_terminal_font_json",
+        support::stdout_json_snapshot(&output)
+    );
+}